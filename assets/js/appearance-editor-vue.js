@@ -26,23 +26,41 @@ export default {
       </b-col>
     </b-row>
 
-    <b-row class="mb-3">
-      <b-col v-for="team in teams">
-        <colorpicker text="Primary Color" v-model="config[team]['team_color_id']" primary :team="team"/>
-        <colorpicker text="Accent Color" v-model="config[team]['custom_color_id']"/>
-        <b-button class="float-right" @click="randomizeTeamLoadout(team)" v-b-tooltip.hover :title="'Randomize entire ' + team + ' team loadout'">
-          <b-icon icon="shuffle"/>
-        </b-button>
-      </b-col>
-    </b-row>
+    <b-alert variant="info" :show="showingGeneratedLoadout">
+      This is the loadout that the bot's loadout generator picks. Uncheck "Use loadout generator" to edit the looks config instead.
+    </b-alert>
 
-    <b-row v-if="Object.keys(config.blue).length" class="mb-4">
-      <b-col v-for="team in teams">
-        <div v-for="itemType in itemTypes">
-          <item-field :item-type="itemType" :items="items[itemType.category]" :team="team" v-model="config[team]" :ref="team"/>
-        </div>
-      </b-col>
-    </b-row>
+    <fieldset :disabled="showingGeneratedLoadout">
+      <b-row class="mb-3">
+        <b-col v-for="team in teams">
+          <colorpicker text="Primary Color" v-model="shownConfig[team]['team_color_id']" primary :team="team"/>
+          <colorpicker text="Accent Color" v-model="shownConfig[team]['custom_color_id']"/>
+          <b-button class="float-right" @click="randomizeTeamLoadout(team)" v-b-tooltip.hover :title="'Randomize entire ' + team + ' team loadout'">
+            <b-icon icon="shuffle"/>
+          </b-button>
+        </b-col>
+      </b-row>
+
+      <b-row v-if="Object.keys(shownConfig.blue).length" class="mb-4">
+        <b-col v-for="team in teams">
+          <div v-for="itemType in itemTypes">
+            <item-field :item-type="itemType" :items="items[itemType.category]" :team="team" v-model="shownConfig[team]" :ref="team"/>
+          </div>
+        </b-col>
+      </b-row>
+    </fieldset>
+
+    <b-input-group prepend="BakkesMod code" class="mb-3">
+      <b-form-input v-model="bakkesmodCode" placeholder="Paste a loadout code to import it, or export this loadout"/>
+      <b-input-group-append>
+        <b-button @click="importBakkesmodCode" :disabled="!bakkesmodCode">Import</b-button>
+        <b-button @click="exportBakkesmodCode">Export</b-button>
+      </b-input-group-append>
+    </b-input-group>
+
+    <b-alert variant="danger" :show="!!saveError" dismissible @dismissed="saveError = null">
+      {{ saveError }}
+    </b-alert>
 
     <div>
       <b-form inline>
@@ -56,6 +74,11 @@ export default {
           View orange car in game
         </b-button>
 
+        <b-form-checkbox v-if="activeBot && activeBot.loadout_generator" v-model="useLoadoutGenerator" class="mr-2"
+          v-b-tooltip.hover title="This bot picks its loadout with a loadout generator when a match starts, so view what it picks instead of the loadout below">
+          Use loadout generator
+        </b-form-checkbox>
+
         <b-form-select v-model="selectedShowcaseType">
           <b-form-select-option v-for="showcaseType in showcaseTypes" :value="showcaseType.id">
             {{ showcaseType.name }}
@@ -64,6 +87,11 @@ export default {
 
         <span style="flex-grow: 1"></span>
 
+        <b-button @click="generateLoadout('team_colors')" class="mr-1" v-b-tooltip.hover title="Randomize both teams with items and colors that match each team">
+          <b-icon icon="palette"/>
+          Team colored loadout
+        </b-button>
+
         <b-button variant="primary" @click="saveAppearance" class="mr-1">
           Save and close
         </b-button>
@@ -78,6 +106,10 @@ export default {
   data() {
     return {
       appearanceModalActive: false,
+      saveError: null,
+      bakkesmodCode: "",
+      useLoadoutGenerator: true,
+      generatedConfig: null,
       config: {
         blue: {},
         orange: {},
@@ -163,6 +195,15 @@ export default {
     };
   },
 
+  computed: {
+    showingGeneratedLoadout: function () {
+      return !!(this.useLoadoutGenerator && this.activeBot && this.activeBot.loadout_generator && this.generatedConfig);
+    },
+    shownConfig: function () {
+      return this.showingGeneratedLoadout ? this.generatedConfig : this.config;
+    },
+  },
+
   methods: {
     getAndParseItems: async function () {
       const response = await fetch("csv/items.csv");
@@ -201,13 +242,49 @@ export default {
     },
     saveAppearance: function () {
       console.log(this.config);
-      invoke("save_looks", { path: this.path, config: this.config });
-      this.$bvModal.hide("appearance-editor-dialog");
-      this.config = {}; // prevents a memory leak from not un-loading configs when the dialog is closed
+      invoke("save_looks", { path: this.path, config: this.config })
+        .then(() => {
+          this.saveError = null;
+          this.$bvModal.hide("appearance-editor-dialog");
+          this.config = {}; // prevents a memory leak from not un-loading configs when the dialog is closed
+        })
+        .catch((error) => {
+          this.saveError = error;
+        });
+    },
+    importBakkesmodCode: function () {
+      invoke("looks_from_bakkesmod_code", { code: this.bakkesmodCode })
+        .then((config) => {
+          this.config = config;
+          this.useLoadoutGenerator = false;
+        })
+        .catch((error) => {
+          this.saveError = error;
+        });
+    },
+    exportBakkesmodCode: function () {
+      invoke("looks_to_bakkesmod_code", { config: this.shownConfig })
+        .then((code) => {
+          this.bakkesmodCode = code;
+        })
+        .catch((error) => {
+          this.saveError = error;
+        });
+    },
+    generateLoadout: function (style) {
+      invoke("generate_loadout", { options: { style } })
+        .then((loadout) => {
+          this.config = loadout.looks;
+          this.useLoadoutGenerator = false;
+        })
+        .catch((error) => {
+          this.saveError = error;
+        });
     },
     spawnCarForViewing: function (team) {
+      // the same loadout that's shown in the editor, even if the generator would pick a different one each time
       invoke("spawn_car_for_viewing", {
-        config: this.config,
+        config: this.shownConfig,
         team,
         showcaseType: this.selectedShowcaseType,
         map: this.map,
@@ -218,6 +295,20 @@ export default {
         this.config = config;
       });
     },
+    loadGeneratedLoadout: function () {
+      this.generatedConfig = null;
+      if (!this.activeBot || !this.activeBot.loadout_generator) {
+        return;
+      }
+
+      invoke("generate_bot_loadout", { configPath: this.activeBot.path, index: 0 })
+        .then((config) => {
+          this.generatedConfig = config;
+        })
+        .catch((error) => {
+          this.saveError = error;
+        });
+    },
     randomizeTeamLoadout: function (team) {
       this.config[team].team_color_id = Math.floor(Math.random() * 70);
       this.config[team].custom_color_id = Math.floor(Math.random() * 105);
@@ -238,6 +329,8 @@ export default {
       handler: function (val) {
         if (val && this.path) {
           this.loadLooks(this.path);
+          this.useLoadoutGenerator = true;
+          this.loadGeneratedLoadout();
         }
       },
     },
@@ -12,6 +12,9 @@ export default {
       <span class="rlbot-brand" style="flex: 1">RLBot Console</span>
     </b-navbar-brand>
     <b-navbar-nav class="ml-auto">
+      <b-button v-if="activeJob !== null" @click="killActiveJob()" variant="danger" class="ml-2">
+        Stop "{{ activeJobCommand }}"
+      </b-button>
       <b-button @click="toggleScrollLock()" variant="dark" class="ml-2">
         <span v-if="scrollLock">Unlock scolling</span>
         <span v-else>Lock scolling</span>
@@ -47,7 +50,8 @@ export default {
       <hr>
 
       <b-form @submit="onSubmit" novalidate>
-        <b-form-input v-on:keyup.down="onDown" v-on:keyup.up="onUp" v-model="inputCommand" id="console-input" placeholder="Enter command..."></b-form-input>
+        <b-form-input v-on:keyup.down="onDown" v-on:keyup.up="onUp" v-on:keydown.tab.prevent="onTab" list="console-completions" v-model="inputCommand" id="console-input" :placeholder="activeJob === null ? 'Enter command... (:help for built-in commands)' : 'Send input to ' + activeJobCommand + '...'"></b-form-input>
+        <b-form-datalist id="console-completions" :options="completions"></b-form-datalist>
       </b-form>
     </b-card>
   </b-container>
@@ -64,6 +68,15 @@ export default {
       texts: 0,
       userChoseLock: false,
       scrollLock: true,
+      completions: [],
+      activeJob: null,
+      activeJobCommand: "",
+      jobFinishedListener: listen("console-job-finished", (event) => {
+        if (event.payload.id === this.activeJob) {
+          this.activeJob = null;
+          this.activeJobCommand = "";
+        }
+      }),
       newTextListener: listen("new-console-texts", (event) => {
         event.payload.forEach((update) => {
           if (update.replace_last) {
@@ -99,6 +112,25 @@ export default {
         this.$refs.scroller.scrollToBottom();
       }
     },
+    killActiveJob: function () {
+      if (this.activeJob !== null) {
+        invoke("kill_console_job", { id: this.activeJob }).catch(console.error);
+      }
+    },
+    onTab: function () {
+      if (this.activeJob !== null) {
+        return;
+      }
+
+      invoke("complete_console_input", { input: this.inputCommand }).then((completions) => {
+        if (completions.length === 1) {
+          this.inputCommand = completions[0];
+          this.completions = [];
+        } else {
+          this.completions = completions;
+        }
+      });
+    },
     onUp: function () {
       if (this.commandsIndex < this.previousCommands.length - 1) {
         if (this.commandsIndex === -1) {
@@ -126,15 +158,31 @@ export default {
         return;
       }
 
-      if (this.previousCommands[0] !== this.inputCommand) {
-        this.previousCommands.unshift(this.inputCommand);
+      if (this.activeJob !== null) {
+        invoke("send_console_job_input", { id: this.activeJob, input: this.inputCommand }).catch(console.error);
+        this.inputCommand = "";
+        return;
       }
 
-      invoke("run_command", { input: this.inputCommand }).catch(console.error);
+      // the backend also de-duplicates the history, so keep the two in sync
+      this.previousCommands = this.previousCommands.filter((command) => command !== this.inputCommand);
+      this.previousCommands.unshift(this.inputCommand);
+
+      const command = this.inputCommand;
+      invoke("run_command", { input: command })
+        .then((id) => {
+          // built-in commands like :start don't start a job
+          if (id !== null) {
+            this.activeJob = id;
+            this.activeJobCommand = command;
+          }
+        })
+        .catch(console.error);
 
       this.inputCommand = "";
       this.savedInputCommand = "";
       this.commandsIndex = -1;
+      this.completions = [];
     },
     startup: function () {
       if (this.$route.path === "/console") {
@@ -150,6 +198,14 @@ export default {
         invoke("get_console_input_commands").then((commands) => {
           this.previousCommands = commands;
         });
+
+        invoke("get_console_jobs").then((jobs) => {
+          if (jobs.length > 0) {
+            const job = jobs[jobs.length - 1];
+            this.activeJob = job.id;
+            this.activeJobCommand = job.command;
+          }
+        });
       }
     },
   },
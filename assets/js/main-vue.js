@@ -86,6 +86,9 @@ export default {
         <b-dropdown-item v-b-modal.package-installer>
           Install missing python package
         </b-dropdown-item>
+        <b-dropdown-item @click="showBotVenvs()">
+          Manage bot virtual environments
+        </b-dropdown-item>
         <b-dropdown-item @click="resetMatchSettingsToDefault()">
           Reset match settings
         </b-dropdown-item>
@@ -293,7 +296,7 @@ export default {
 
     <b-modal id="bot-info-modal" size="xl" :title="activeBot.name" v-if="activeBot && activeBot.info" hide-footer centered>
 
-      <img v-if="activeBot.logo" class="bot-logo" v-bind:src="activeBot.logo">
+      <img v-if="activeBotLogo" class="bot-logo" v-bind:src="activeBotLogo">
       <p><span class="bot-info-key">Developers:</span> {{activeBot.info.developer}}</p>
       <p><span class="bot-info-key">Description:</span> {{activeBot.info.description}}</p>
       <p><span class="bot-info-key">Fun Fact:</span> {{activeBot.info.fun_fact}}</p>
@@ -304,6 +307,8 @@ export default {
         <span class="bot-info-key">Tags:</span>
         <b-badge v-for="tag in activeBot.info.tags" class="ml-1">{{tag}}</b-badge>
       </p>
+      <p v-if="activeBot.supports_early_start"><span class="bot-info-key">Early start:</span> Starts before the match begins</p>
+      <p v-if="activeBot.maximum_tick_rate_preference"><span class="bot-info-key">Maximum tick rate:</span> {{activeBot.maximum_tick_rate_preference}} ticks per second</p>
       <p class="bot-file-path">{{activeBot.path}}</p>
 
       <div>
@@ -425,6 +430,29 @@ export default {
       </b-list-group>
     </b-modal>
 
+    <b-modal id="bot-venvs-modal" title="Bot Virtual Environments" size="xl" hide-footer centered>
+      <p v-if="Object.keys(botVenvs).length === 0">No bots have a virtual environment yet. Bots that ask for one get it when they're added to a team.</p>
+      <b-list-group v-else>
+        <b-list-group-item v-for="(venv, path) in botVenvs" :key="path" class="d-flex align-items-center">
+          <div class="flex-grow-1">
+            <b>{{ venv.name }}</b> <small class="text-muted">{{ path }}</small>
+            <br>
+            <small>
+              {{ formatVenvSize(venv.size) }} &middot; created from {{ venv.base_python }} &middot;
+              {{ venv.last_used ? 'last used ' + new Date(venv.last_used * 1000).toLocaleString() : 'never used' }}
+            </small>
+          </div>
+          <b-button size="sm" variant="outline-primary" class="ml-2" :disabled="busyBotVenv !== null" @click="rebuildBotVenv(path)">
+            <b-spinner small v-if="busyBotVenv === path"></b-spinner>
+            Rebuild
+          </b-button>
+          <b-button size="sm" variant="outline-danger" class="ml-2" :disabled="busyBotVenv !== null" @click="deleteBotVenv(path, venv.name)">
+            Delete
+          </b-button>
+        </b-list-group-item>
+      </b-list-group>
+    </b-modal>
+
     <b-modal id="no-rlbot-flag-modal" title="Error while starting match" centered>
       <p>{{ errorStartingMatchContent }}<br /><br />If this message still appears, try restarting RLBot.</p>
       <template v-slot:modal-footer>
@@ -474,6 +502,7 @@ export default {
       scriptPool: [],
       blueTeam: [HUMAN],
       orangeTeam: [],
+      activeBotLogo: null,
       teamSelection: "orange",
       matchOptions: null,
       matchSettings: {
@@ -534,6 +563,8 @@ export default {
       allowMiniConsoleClose: false,
       errorStartingMatchContent: "",
       logUploadUrl: "",
+      botVenvs: {},
+      busyBotVenv: null,
       updateDownloadProgressPercent: listen(
         "update-download-progress",
         (event) => {
@@ -557,6 +588,14 @@ export default {
         const path = event.payload;
         if (path) this.showAppearanceEditor(path);
       }),
+      botsChanged: listen("bots-changed", (event) => {
+        const { added, removed, modified } = event.payload;
+        const stalePaths = removed.concat(modified).map((bot) => bot.path);
+        this.botPool = this.botPool.filter(
+          (bot) => !stalePaths.includes(bot.path)
+        );
+        this.botsReceived(added.concat(modified));
+      }),
     };
   },
 
@@ -760,6 +799,64 @@ export default {
         this.$store.commit("setActiveBot", bot);
         this.$bvModal.show("language-warning-modal");
       }
+
+      // set up the bot's virtual environment now instead of when the match is starting
+      if (bot.supports_standalone && bot.use_virtual_environment) {
+        invoke("prepare_bot_venv", { configPath: bot.path }).catch((error) => {
+          this.snackbarContent = `Failed to set up the virtual environment for ${bot.name}: ${error}`;
+          this.showSnackbar = true;
+        });
+      }
+    },
+    showBotVenvs: function () {
+      invoke("get_bot_venvs").then((venvs) => {
+        this.botVenvs = venvs;
+        this.$bvModal.show("bot-venvs-modal");
+      });
+    },
+    formatVenvSize: function (bytes) {
+      return (bytes / (1024 * 1024)).toFixed(1) + " MB";
+    },
+    rebuildBotVenv: function (path) {
+      this.busyBotVenv = path;
+      invoke("rebuild_bot_venv", { configPath: path })
+        .then((venv) => {
+          Vue.set(this.botVenvs, path, venv);
+        })
+        .catch((error) => {
+          this.snackbarContent = "Failed to rebuild the virtual environment: " + error;
+          this.showSnackbar = true;
+        })
+        .finally(() => {
+          this.busyBotVenv = null;
+        });
+    },
+    deleteBotVenv: function (path, name) {
+      this.$bvModal
+        .msgBoxConfirm(`Delete the virtual environment for ${name}? It will be created again the next time the bot plays.`, {
+          title: "Delete virtual environment?",
+          okVariant: "danger",
+          okTitle: "Delete",
+          centered: true,
+        })
+        .then((confirmed) => {
+          if (!confirmed) {
+            return;
+          }
+
+          this.busyBotVenv = path;
+          invoke("delete_bot_venv", { configPath: path })
+            .then(() => {
+              Vue.delete(this.botVenvs, path);
+            })
+            .catch((error) => {
+              this.snackbarContent = "Failed to delete the virtual environment: " + error;
+              this.showSnackbar = true;
+            })
+            .finally(() => {
+              this.busyBotVenv = null;
+            });
+        });
     },
     setRandomMap: async function () {
       if (this.randomMapPool.length === 0) {
@@ -983,7 +1080,19 @@ export default {
     },
     applyLanguageWarnings: function (bots) {
       if (this.languageSupport) {
+        // the runtime that each language needs, which is also the name of the warning
+        const languageRuntimes = { javascript: "node", java: "java", scratch: "chrome", csharp: "dotnet" };
+
         bots.forEach((bot) => {
+          // bots have their language detected when they're scanned, but scripts don't
+          if (bot.language) {
+            const runtime = languageRuntimes[bot.language];
+            if (runtime && !this.languageSupport[runtime]) {
+              bot.warn = runtime;
+            }
+            return;
+          }
+
           if (bot.info && bot.info.language) {
             const language = bot.info.language.toLowerCase();
 
@@ -1302,5 +1411,17 @@ export default {
   watch: {
     // call again the method if the route changes
     $route: "startup",
+    activeBot: function (bot) {
+      this.activeBotLogo = bot ? bot.logo : null;
+
+      // the logo in the bot's card is too small for the info dialog
+      if (bot && bot.logo_path) {
+        invoke("get_logo_thumbnail", { path: bot.logo_path, size: "large" }).then((logo) => {
+          if (logo && this.activeBot === bot) {
+            this.activeBotLogo = logo;
+          }
+        });
+      }
+    },
   },
 };
@@ -28,7 +28,11 @@ export default {
           <b-form-input class="text-center" style="width: 100%" :placeholder="rec_python || (is_rec_isolated && is_windows) ? 'Confused? Click the button just below!' : 'Path to python.exe'" id="python-exe-path" v-model="python_path" size="md"></b-form-input>
           <span v-if="!is_rec_isolated">
             <b-button v-if="is_windows" variant="success" class="mt-3" @click="installPython()">&nbsp;Install custom isolated Python 3.7</b-button>
-            <b-button v-else variant="success" class="mt-3" @click="createVenv()">&nbsp;Create isolated Python (from {{ rec_python }})</b-button>
+            <span v-else>
+              <b-button v-if="rec_python" variant="success" class="mt-3" @click="createVenv()">&nbsp;Create isolated Python (from {{ rec_python }})</b-button>
+              <b-button variant="success" class="mt-3" @click="installPython()">&nbsp;Install standalone isolated Python</b-button>
+              <b-form-input class="text-center mt-3" style="width: 100%" placeholder="Standalone Python tarball URL or local file (leave empty for the default download)" v-model="standalone_source" @change="setStandaloneSource()" size="sm"></b-form-input>
+            </span>
             <br>
           </span>
           <b-button v-if="rec_python && rec_python != python_path" :variant="!is_rec_isolated ? 'warning' : 'success'" class="mt-3" @click="partialPythonSetup()"><b-icon v-if="!is_rec_isolated" icon="exclamation-triangle-fill"/>&nbsp;Use found Python path</b-button>
@@ -90,6 +94,7 @@ export default {
       python_path: "",
       rec_python: null,
       is_windows: false,
+      standalone_source: "",
       is_rec_isolated: false,
       advanced: false,
       downloadModalTitle: "",
@@ -138,13 +143,35 @@ export default {
         });
     },
     installPython: function () {
+      invoke("standalone_python_env_exists")
+        .then((exists) => {
+          if (!exists) {
+            return false;
+          }
+
+          return this.$bvModal
+            .msgBoxConfirm("There is already an isolated Python environment, installing a standalone Python will delete it and any packages in it. Continue?", {
+              title: "Replace existing Python environment?",
+              okVariant: "danger",
+              okTitle: "Replace",
+              centered: true,
+            })
+            .then((confirmed) => (confirmed ? true : null));
+        })
+        .then((replaceEnv) => {
+          if (replaceEnv !== null) {
+            this.runPythonInstall(replaceEnv);
+          }
+        });
+    },
+    runPythonInstall: function (replaceEnv) {
       this.showProgressSpinner = true;
-      this.downloadModalTitle = "Installing Isolated Python 3.7";
+      this.downloadModalTitle = this.is_windows ? "Installing Isolated Python 3.7" : "Installing Standalone Python";
       this.downloadStatus = "Starting installation...";
       this.downloadProgressPercent = 0;
       this.$bvModal.show("download-modal");
 
-      invoke("install_python")
+      invoke("install_python", { replaceEnv: replaceEnv })
         .then(() => {
           this.$bvModal.hide("download-modal");
           this.snackbarContent =
@@ -173,6 +200,9 @@ export default {
           this.showSnackbar = true;
         });
     },
+    setStandaloneSource: function () {
+      invoke("set_standalone_python_source", { source: this.standalone_source || null });
+    },
     applyPythonSetup: function () {
       this.showProgressSpinner = true;
 
@@ -241,6 +271,11 @@ export default {
 
       invoke("is_windows").then((isWindows) => {
         this.is_windows = isWindows;
+        if (!isWindows) {
+          invoke("get_standalone_python_source").then((source) => {
+            this.standalone_source = source || "";
+          });
+        }
       });
     },
   },
@@ -1,4 +1,10 @@
 pub(crate) mod bot_creation;
+pub(crate) mod catalog;
+pub(crate) mod cfg_document;
 pub(crate) mod cfg_helper;
 pub(crate) mod downloader;
+pub(crate) mod duplicates;
+pub(crate) mod folder_watcher;
+pub(crate) mod loadout_randomizer;
+pub(crate) mod logo_cache;
 pub(crate) mod zip_extract_fixed;
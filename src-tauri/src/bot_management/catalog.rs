@@ -0,0 +1,410 @@
+use crate::{ccprintln, rlbot::parsing::bot_config_bundle::BotConfigBundle, settings::BotFolders};
+use glob::glob;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fs::{metadata, read_to_string},
+    path::Path,
+    time::UNIX_EPOCH,
+};
+use tauri::Window;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogSort {
+    #[default]
+    Name,
+    /// Newest config files first
+    RecentlyAdded,
+    /// Highest rated first, with unrated bots at the end
+    Rating,
+}
+
+/// What to search the bot catalog for, every field is optional
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CatalogQuery {
+    /// Words that must all appear in the bot's name, description, or developer
+    pub search: String,
+    /// Only include bots written in one of these languages
+    pub languages: Vec<String>,
+    /// Only include bots that have every one of these tags
+    pub tags: Vec<String>,
+    pub sort: CatalogSort,
+    /// The page to return, starting at 0
+    pub page: usize,
+    pub page_size: usize,
+}
+
+impl Default for CatalogQuery {
+    fn default() -> Self {
+        Self {
+            search: String::new(),
+            languages: Vec::new(),
+            tags: Vec::new(),
+            sort: CatalogSort::default(),
+            page: 0,
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+/// A bot in the catalog, with the information that it can be sorted by
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogEntry {
+    pub bot: BotConfigBundle,
+    /// When the config file was added, in seconds since the Unix epoch
+    pub added: u64,
+    pub rating: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogPage {
+    pub entries: Vec<CatalogEntry>,
+    /// How many bots matched the query across every page
+    pub total: usize,
+    pub page: usize,
+    pub page_count: usize,
+    /// Every language of the bots that matched the search, with how many bots use it, for building the filters
+    pub languages: BTreeMap<String, usize>,
+    /// Every tag of the bots that matched the search, with how many bots have it, for building the filters
+    pub tags: BTreeMap<String, usize>,
+}
+
+/// Load bot ratings from every `ratings.json` in the visible bot folders, which map a bot's name to it's rating
+pub fn load_ratings(window: &Window, bot_folders: &BotFolders) -> HashMap<String, f32> {
+    bot_folders
+        .folders
+        .iter()
+        .filter(|(_, props)| props.visible)
+        .filter_map(|(path, _)| match glob(&format!("{path}/**/ratings.json")) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                ccprintln!(window, "{e}");
+                None
+            }
+        })
+        .flatten()
+        .flatten()
+        .filter_map(|path| match read_to_string(&path).map(|text| serde_json::from_str::<HashMap<String, f32>>(&text)) {
+            Ok(Ok(ratings)) => Some(ratings),
+            Ok(Err(e)) => {
+                ccprintln!(window, "Error parsing {}: {e}", path.display());
+                None
+            }
+            Err(e) => {
+                ccprintln!(window, "Error reading {}: {e}", path.display());
+                None
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+/// Get when a config file was added, falling back to when it was last modified on platforms that don't record creation times
+fn added_time(path: &str) -> u64 {
+    metadata(Path::new(path))
+        .and_then(|metadata| metadata.created().or_else(|_| metadata.modified()))
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn matches_search(bot: &BotConfigBundle, words: &[String]) -> bool {
+    let mut haystack = bot.name.to_lowercase();
+    if let Some(info) = &bot.info {
+        haystack.push('\n');
+        haystack.push_str(&info.description.to_lowercase());
+        haystack.push('\n');
+        haystack.push_str(&info.developer.to_lowercase());
+    }
+
+    words.iter().all(|word| haystack.contains(word.as_str()))
+}
+
+fn language_of(bot: &BotConfigBundle) -> Option<&str> {
+    bot.info.as_ref().map(|info| info.language.trim()).filter(|language| !language.is_empty())
+}
+
+fn tags_of(bot: &BotConfigBundle) -> impl Iterator<Item = &str> {
+    bot.info.iter().flat_map(|info| info.tags.iter().map(|tag| tag.trim()).filter(|tag| !tag.is_empty()))
+}
+
+fn matches_filters(bot: &BotConfigBundle, languages: &[String], tags: &[String]) -> bool {
+    let language = language_of(bot).map(str::to_lowercase).unwrap_or_default();
+    if !languages.is_empty() && !languages.iter().any(|filter| language.contains(filter.as_str())) {
+        return false;
+    }
+
+    tags.iter().all(|filter| tags_of(bot).any(|tag| tag.eq_ignore_ascii_case(filter)))
+}
+
+fn to_lowercase_list(items: &[String]) -> Vec<String> {
+    items.iter().map(|item| item.trim().to_lowercase()).filter(|item| !item.is_empty()).collect()
+}
+
+/// Searches, filters, sorts, and paginates bots
+///
+/// The language and tag counts only take the search into account, so the filters can show how many bots picking them would give
+///
+/// # Arguments
+///
+/// * `bots` - Every bot in the catalog
+/// * `query` - What to search for
+/// * `ratings` - Bot ratings by name, from `load_ratings`
+pub fn query(bots: Vec<BotConfigBundle>, query: &CatalogQuery, ratings: &HashMap<String, f32>) -> CatalogPage {
+    let words = query.search.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>();
+    let languages = to_lowercase_list(&query.languages);
+    let tags = to_lowercase_list(&query.tags);
+
+    let searched = bots.into_iter().filter(|bot| matches_search(bot, &words)).collect::<Vec<_>>();
+
+    let mut language_counts = BTreeMap::new();
+    let mut tag_counts = BTreeMap::new();
+    for bot in &searched {
+        if let Some(language) = language_of(bot) {
+            *language_counts.entry(language.to_owned()).or_insert(0) += 1;
+        }
+
+        for tag in tags_of(bot) {
+            *tag_counts.entry(tag.to_lowercase()).or_insert(0) += 1;
+        }
+    }
+
+    let mut entries = searched
+        .into_iter()
+        .filter(|bot| matches_filters(bot, &languages, &tags))
+        .map(|bot| CatalogEntry {
+            added: added_time(&bot.path),
+            rating: ratings.get(&bot.name).copied(),
+            bot,
+        })
+        .collect::<Vec<_>>();
+
+    let by_name = |a: &CatalogEntry, b: &CatalogEntry| a.bot.name.to_lowercase().cmp(&b.bot.name.to_lowercase());
+    match query.sort {
+        CatalogSort::Name => entries.sort_by(by_name),
+        CatalogSort::RecentlyAdded => entries.sort_by(|a, b| b.added.cmp(&a.added).then_with(|| by_name(a, b))),
+        CatalogSort::Rating => entries.sort_by(|a, b| {
+            match (a.rating, b.rating) {
+                (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+            .then_with(|| by_name(a, b))
+        }),
+    }
+
+    let page_size = query.page_size.max(1);
+    let total = entries.len();
+    let page_count = (total + page_size - 1) / page_size;
+
+    CatalogPage {
+        entries: entries.into_iter().skip(query.page * page_size).take(page_size).collect(),
+        total,
+        page: query.page,
+        page_count,
+        languages: language_counts,
+        tags: tag_counts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rlbot::parsing::bot_config_bundle::DevInfo;
+    use std::fs::{remove_file, write};
+
+    fn bot(name: &str, language: &str, tags: &[&str], description: &str, developer: &str) -> BotConfigBundle {
+        BotConfigBundle {
+            name: name.to_owned(),
+            path: format!("rlbot-catalog-test/{name}.cfg"),
+            info: Some(DevInfo {
+                developer: developer.to_owned(),
+                description: description.to_owned(),
+                fun_fact: String::new(),
+                github: String::new(),
+                language: language.to_owned(),
+                tags: tags.iter().map(|tag| (*tag).to_owned()).collect(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn bots() -> Vec<BotConfigBundle> {
+        vec![
+            bot("Kamael", "Python", &["1v1", "teamplay"], "Hard hitting", "Kamael"),
+            bot("Atba", "python", &["1v1"], "A basic bot", "RLBot"),
+            bot("ReliefBot", "C#", &["teamplay", "goalie"], "Flies a lot", "tarehart"),
+            bot("Nexto", "Python", &["1v1", "Teamplay"], "Machine learning", "Rolv"),
+            bot("Beast from the East", "Java", &["1v1"], "Big and slow", "NotBeast"),
+            bot("botimus Prime", "Python", &["teamplay"], "Rotates well", "Darxeal"),
+            BotConfigBundle {
+                name: "Sniper".to_owned(),
+                path: "rlbot-catalog-test/Sniper.cfg".to_owned(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn names(page: &CatalogPage) -> Vec<&str> {
+        page.entries.iter().map(|entry| entry.bot.name.as_str()).collect()
+    }
+
+    fn run(query: CatalogQuery) -> CatalogPage {
+        super::query(bots(), &query, &HashMap::new())
+    }
+
+    #[test]
+    fn search() {
+        // the name, description, and developer are all searched
+        assert_eq!(
+            names(&run(CatalogQuery {
+                search: "BOT".to_owned(),
+                ..Default::default()
+            })),
+            ["Atba", "botimus Prime", "ReliefBot"]
+        );
+
+        // every word has to match, but not next to each other
+        let page = run(CatalogQuery {
+            search: " hitting  hard ".to_owned(),
+            ..Default::default()
+        });
+        assert_eq!(names(&page), ["Kamael"]);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.languages, BTreeMap::from([("Python".to_owned(), 1)]));
+        assert_eq!(page.tags, BTreeMap::from([("1v1".to_owned(), 1), ("teamplay".to_owned(), 1)]));
+
+        assert_eq!(run(CatalogQuery::default()).total, 7);
+        assert_eq!(
+            run(CatalogQuery {
+                search: "nothing matches this".to_owned(),
+                ..Default::default()
+            })
+            .total,
+            0
+        );
+    }
+
+    #[test]
+    fn language_filter() {
+        let page = run(CatalogQuery {
+            languages: vec!["PYTHON".to_owned()],
+            ..Default::default()
+        });
+        assert_eq!(names(&page), ["Atba", "botimus Prime", "Kamael", "Nexto"]);
+
+        // the counts ignore the filters, so they show what picking another filter would give
+        assert_eq!(
+            page.languages,
+            BTreeMap::from([("C#".to_owned(), 1), ("Java".to_owned(), 1), ("Python".to_owned(), 3), ("python".to_owned(), 1)])
+        );
+
+        let page = run(CatalogQuery {
+            languages: vec!["c#".to_owned(), " java ".to_owned()],
+            ..Default::default()
+        });
+        assert_eq!(names(&page), ["Beast from the East", "ReliefBot"]);
+    }
+
+    #[test]
+    fn tag_filter() {
+        let page = run(CatalogQuery {
+            tags: vec!["1V1".to_owned(), "teamplay".to_owned()],
+            ..Default::default()
+        });
+        assert_eq!(names(&page), ["Kamael", "Nexto"]);
+        assert_eq!(page.tags, BTreeMap::from([("1v1".to_owned(), 4), ("goalie".to_owned(), 1), ("teamplay".to_owned(), 4)]));
+
+        let page = run(CatalogQuery {
+            search: "flies".to_owned(),
+            tags: vec!["1v1".to_owned()],
+            ..Default::default()
+        });
+        assert!(page.entries.is_empty());
+        assert_eq!(page.tags, BTreeMap::from([("goalie".to_owned(), 1), ("teamplay".to_owned(), 1)]));
+    }
+
+    #[test]
+    fn sort_by_name() {
+        assert_eq!(
+            names(&run(CatalogQuery::default())),
+            ["Atba", "Beast from the East", "botimus Prime", "Kamael", "Nexto", "ReliefBot", "Sniper"]
+        );
+    }
+
+    #[test]
+    fn sort_by_rating() {
+        let ratings = HashMap::from([("Nexto".to_owned(), 9.5), ("Kamael".to_owned(), 8.), ("ReliefBot".to_owned(), 8.), ("Atba".to_owned(), 2.)]);
+        let query = CatalogQuery {
+            sort: CatalogSort::Rating,
+            ..Default::default()
+        };
+
+        let page = super::query(bots(), &query, &ratings);
+        assert_eq!(names(&page), ["Nexto", "Kamael", "ReliefBot", "Atba", "Beast from the East", "botimus Prime", "Sniper"]);
+        assert_eq!(page.entries[0].rating, Some(9.5));
+        assert_eq!(page.entries[4].rating, None);
+    }
+
+    #[test]
+    fn sort_by_recently_added() {
+        let mut bots = bots();
+        let path = std::env::temp_dir().join(format!("rlbot-catalog-{}.cfg", std::process::id()));
+        write(&path, "[Locations]\nname = Nexto\n").unwrap();
+        bots[3].path = path.to_string_lossy().to_string();
+
+        let query = CatalogQuery {
+            sort: CatalogSort::RecentlyAdded,
+            ..Default::default()
+        };
+        let page = super::query(bots, &query, &HashMap::new());
+        remove_file(path).ok();
+
+        // the config files of the other bots don't exist, so they tie and are sorted by name
+        assert_eq!(names(&page), ["Nexto", "Atba", "Beast from the East", "botimus Prime", "Kamael", "ReliefBot", "Sniper"]);
+        assert!(page.entries[0].added > 0);
+        assert_eq!(page.entries[1].added, 0);
+    }
+
+    #[test]
+    fn pagination() {
+        let page = |page, page_size| {
+            run(CatalogQuery {
+                page,
+                page_size,
+                ..Default::default()
+            })
+        };
+
+        let first = page(0, 3);
+        assert_eq!(names(&first), ["Atba", "Beast from the East", "botimus Prime"]);
+        assert_eq!((first.total, first.page, first.page_count), (7, 0, 3));
+
+        assert_eq!(names(&page(1, 3)), ["Kamael", "Nexto", "ReliefBot"]);
+
+        // the last page only has what's left over
+        let last = page(2, 3);
+        assert_eq!(names(&last), ["Sniper"]);
+        assert_eq!((last.total, last.page, last.page_count), (7, 2, 3));
+
+        let past_the_end = page(5, 3);
+        assert!(past_the_end.entries.is_empty());
+        assert_eq!((past_the_end.total, past_the_end.page, past_the_end.page_count), (7, 5, 3));
+
+        // a page size of 0 is treated as 1 instead of dividing by zero
+        let single = page(1, 0);
+        assert_eq!(names(&single), ["Beast from the East"]);
+        assert_eq!(single.page_count, 7);
+
+        let exact = page(0, 7);
+        assert_eq!(exact.entries.len(), 7);
+        assert_eq!(exact.page_count, 1);
+    }
+}
@@ -0,0 +1,423 @@
+use configparser::ini::Ini;
+use std::{fmt, ops::Range};
+
+/// The section that keys before the first section header belong to, the same as in `configparser`
+const DEFAULT_SECTION: &str = "default";
+
+const COMMENT_SYMBOL: char = ';';
+/// Like in Python's configparser, this only starts a comment at the start of a line, so values like `language = C#` keep it
+const LINE_COMMENT_SYMBOL: char = '#';
+const DELIMITERS: [char; 2] = ['=', ':'];
+
+/// Lines that continue a value are indented by this much, which is also what Python's configparser needs
+const CONTINUATION_INDENT: &str = "    ";
+
+enum Line {
+    /// A `[section]` header, with the section's name in lowercase
+    Header { section: String, text: String },
+    /// A key, with its name in lowercase, and the lines that its value is on
+    Entry { key: String, text: Vec<String> },
+    /// Blank lines and comments
+    Other(String),
+}
+
+impl Line {
+    fn text(&self) -> &[String] {
+        match self {
+            Self::Header { text, .. } | Self::Other(text) => std::slice::from_ref(text),
+            Self::Entry { text, .. } => text,
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        matches!(self, Self::Other(text) if text.trim().is_empty())
+    }
+}
+
+/// Checks if a whole line is a `#` comment, which `configparser` can't be told about without it also cutting `#` out of values
+pub fn is_line_comment(line: &str) -> bool {
+    line.trim_start().starts_with(LINE_COMMENT_SYMBOL)
+}
+
+/// Get the part of a line before any comment
+fn strip_comment(line: &str) -> &str {
+    if is_line_comment(line) {
+        return &line[..0];
+    }
+
+    line.find(COMMENT_SYMBOL).map_or(line, |index| &line[..index])
+}
+
+fn parse_header(line: &str) -> Option<String> {
+    let (name, _) = strip_comment(line).trim().strip_prefix('[')?.rsplit_once(']')?;
+    Some(name.trim().to_lowercase())
+}
+
+fn parse_key(line: &str) -> String {
+    let content = strip_comment(line).trim();
+    content.find(&DELIMITERS[..]).map_or(content, |index| content[..index].trim()).to_lowercase()
+}
+
+/// Get the value of a key the same way `configparser` reads it, with continued lines joined by newlines
+fn entry_value(text: &[String]) -> Option<String> {
+    let first = strip_comment(&text[0]).trim();
+    let mut value = first.find(&DELIMITERS[..]).map(|index| first[index + 1..].trim().to_owned());
+
+    for line in &text[1..] {
+        let line = strip_comment(line).trim();
+        if !line.is_empty() {
+            let value = value.get_or_insert_with(String::new);
+            value.push('\n');
+            value.push_str(line);
+        }
+    }
+
+    value
+}
+
+fn same_value(old: Option<String>, new: Option<&str>) -> bool {
+    match (old, new) {
+        (Some(old), Some(new)) => old.lines().map(str::trim).eq(new.lines().map(str::trim)),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Write a new value for a key, keeping how the key and delimiter were written, any comment after the value, and any comments between its lines
+fn rewrite_entry(text: &[String], value: Option<&str>) -> Vec<String> {
+    let first = &text[0];
+    let (content, comment) = first.split_at(strip_comment(first).len());
+
+    let (key, prefix) = match content.find(&DELIMITERS[..]) {
+        Some(index) => {
+            let after = &content[index + 1..];
+            let spacing = if after.trim().is_empty() {
+                " "
+            } else {
+                &after[..after.len() - after.trim_start().len()]
+            };
+            (content[..index].trim_end(), format!("{}{spacing}", &content[..=index]))
+        }
+        None => (content.trim_end(), format!("{} = ", content.trim_end())),
+    };
+
+    let mut value_lines = value.map(str::lines);
+    let first_line = match value_lines.as_mut() {
+        Some(lines) => format!("{prefix}{}", lines.next().unwrap_or_default().trim()),
+        None => key.to_owned(),
+    };
+
+    let mut new_text = vec![if comment.is_empty() {
+        first_line.trim_end().to_owned()
+    } else {
+        format!("{} {comment}", first_line.trim_end())
+    }];
+
+    new_text.extend(value_lines.into_iter().flatten().map(|line| format!("{CONTINUATION_INDENT}{}", line.trim())));
+    new_text.extend(text[1..].iter().filter(|line| strip_comment(line).trim().is_empty()).cloned());
+
+    new_text
+}
+
+/// A cfg file that remembers every line of the original file, so changing a value doesn't lose any comments or reorder anything
+pub struct CfgDocument {
+    lines: Vec<Line>,
+    line_ending: &'static str,
+    final_newline: bool,
+}
+
+impl CfgDocument {
+    /// Parses a cfg file the same way `load_cfg` does, where indented lines continue the value of the key before them
+    pub fn parse(text: &str) -> Self {
+        let mut lines = Vec::new();
+
+        for line in text.lines() {
+            if strip_comment(line).trim().is_empty() {
+                lines.push(Line::Other(line.to_owned()));
+                continue;
+            }
+
+            if let Some(section) = parse_header(line) {
+                lines.push(Line::Header { section, text: line.to_owned() });
+                continue;
+            }
+
+            // like in configparser, blank lines and comments can be between a key and the rest of its value
+            if line.starts_with(char::is_whitespace) {
+                let last_key = lines
+                    .iter()
+                    .rposition(|line| !matches!(line, Line::Other(_)))
+                    .filter(|&index| matches!(lines[index], Line::Entry { .. }));
+
+                if let Some(index) = last_key {
+                    let between = lines.drain(index + 1..).flat_map(|line| line.text().to_vec()).collect::<Vec<_>>();
+                    if let Line::Entry { text, .. } = &mut lines[index] {
+                        text.extend(between);
+                        text.push(line.to_owned());
+                    }
+                    continue;
+                }
+            }
+
+            lines.push(Line::Entry {
+                key: parse_key(line),
+                text: vec![line.to_owned()],
+            });
+        }
+
+        Self {
+            lines,
+            line_ending: if text.contains("\r\n") { "\r\n" } else { "\n" },
+            final_newline: text.is_empty() || text.ends_with('\n'),
+        }
+    }
+
+    /// Get the ranges of lines that belong to a section, which can appear more than once
+    fn section_ranges(&self, section: &str) -> Vec<Range<usize>> {
+        let section = section.to_lowercase();
+        let mut ranges = Vec::new();
+        let mut start = (section == DEFAULT_SECTION).then_some(0);
+
+        for (index, line) in self.lines.iter().enumerate() {
+            if let Line::Header { section: name, .. } = line {
+                if let Some(start) = start.take() {
+                    ranges.push(start..index);
+                }
+
+                if *name == section {
+                    start = Some(index + 1);
+                }
+            }
+        }
+
+        if let Some(start) = start {
+            ranges.push(start..self.lines.len());
+        }
+
+        ranges
+    }
+
+    /// Set the value of a key, keeping its place in the file and any comment after it
+    ///
+    /// New keys are added after the last key in their section, and new sections are added to the end of the file
+    ///
+    /// # Arguments
+    ///
+    /// * `section`: The section that the key is in
+    /// * `key`: The key to set
+    /// * `value`: What to set the value to, or None for a key without a value
+    pub fn set(&mut self, section: &str, key: &str, value: Option<&str>) {
+        let ranges = self.section_ranges(section);
+        let key_name = key.to_lowercase();
+
+        // like in configparser, the last time a key appears is the one that counts
+        let existing = ranges
+            .iter()
+            .rev()
+            .flat_map(|range| range.clone().rev())
+            .find(|&index| matches!(&self.lines[index], Line::Entry { key, .. } if *key == key_name));
+
+        if let Some(index) = existing {
+            if let Line::Entry { text, .. } = &mut self.lines[index] {
+                // unchanged values are left exactly how they were written
+                if !same_value(entry_value(text), value) {
+                    *text = rewrite_entry(text, value);
+                }
+            }
+            return;
+        }
+
+        let entry = Line::Entry {
+            key: key_name,
+            text: rewrite_entry(&[key.to_owned()], value),
+        };
+
+        if let Some(range) = ranges.last() {
+            // after the last key instead of at the end of the section, so blank lines before the next section stay where they are
+            let index = range
+                .clone()
+                .rev()
+                .find(|&index| matches!(self.lines[index], Line::Entry { .. }))
+                .map_or(range.start, |index| index + 1);
+            self.lines.insert(index, entry);
+            return;
+        }
+
+        if self.lines.last().map_or(false, |line| !line.is_blank()) {
+            self.lines.push(Line::Other(String::new()));
+        }
+
+        self.lines.push(Line::Header {
+            section: section.to_lowercase(),
+            text: format!("[{section}]"),
+        });
+        self.lines.push(entry);
+    }
+
+    /// Applies every value in a loaded cfg file to the document
+    ///
+    /// Keys that are missing from a section in `conf` are removed, but sections that aren't in `conf` at all are left alone
+    pub fn merge(&mut self, conf: &Ini) {
+        for (section, values) in conf.get_map_ref() {
+            let removed = self
+                .section_ranges(section)
+                .into_iter()
+                .flatten()
+                .filter(|&index| matches!(&self.lines[index], Line::Entry { key, .. } if !values.contains_key(key)))
+                .collect::<Vec<_>>();
+
+            for index in removed.into_iter().rev() {
+                self.lines.remove(index);
+            }
+
+            for (key, value) in values {
+                self.set(section, key, value.as_deref());
+            }
+        }
+    }
+}
+
+impl fmt::Display for CfgDocument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, line) in self.lines.iter().flat_map(Line::text).enumerate() {
+            if index != 0 {
+                f.write_str(self.line_ending)?;
+            }
+            f.write_str(line)?;
+        }
+
+        if self.final_newline && !self.lines.is_empty() {
+            f.write_str(self.line_ending)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOT_CFG: &str = "; written by hand
+[Locations]
+; the looks are shared with other bots
+looks_config = ./appearance.cfg  ; relative to this file
+
+# Path to python file
+python_file = ./bot.py
+
+[Details]
+developer: Someone
+language = C#
+description = A bot that does things,
+    and then some more things
+
+    ; a comment between the lines of a value
+    and even more things
+fun_fact
+
+[Locations]
+name = Bot
+";
+
+    /// Builds a loaded cfg file from (section, key, value) triples
+    fn conf(values: &[(&str, &str, Option<&str>)]) -> Ini {
+        let mut conf = Ini::new();
+        for (section, key, value) in values {
+            conf.set(section, key, value.map(ToOwned::to_owned));
+        }
+        conf
+    }
+
+    #[test]
+    fn round_trip() {
+        let crlf = BOT_CFG.replace('\n', "\r\n");
+        let no_final_newline = BOT_CFG.trim_end();
+
+        for text in [BOT_CFG, &crlf, no_final_newline, "", "\n\n", "key = value"] {
+            assert_eq!(CfgDocument::parse(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn set_keeps_comments() {
+        let mut document = CfgDocument::parse(BOT_CFG);
+        document.set("Locations", "looks_config", Some("./other_appearance.cfg"));
+
+        let text = document.to_string();
+        assert!(text.contains("\nlooks_config = ./other_appearance.cfg ; relative to this file\n"));
+        assert_eq!(text.replace("./other_appearance.cfg ;", "./appearance.cfg  ;"), BOT_CFG);
+    }
+
+    #[test]
+    fn set_unchanged_value() {
+        let mut document = CfgDocument::parse(BOT_CFG);
+        document.set("locations", "LOOKS_CONFIG", Some("./appearance.cfg"));
+        document.set("Details", "description", Some("A bot that does things,\nand then some more things\nand even more things"));
+
+        assert_eq!(document.to_string(), BOT_CFG);
+    }
+
+    #[test]
+    fn set_duplicate_section() {
+        let mut document = CfgDocument::parse(BOT_CFG);
+        document.set("Locations", "name", Some("Other Bot"));
+        document.set("Locations", "logo_file", Some("logo.png"));
+
+        let text = document.to_string();
+        assert!(text.ends_with("[Locations]\nname = Other Bot\nlogo_file = logo.png\n"));
+        assert_eq!(text.matches("name = ").count(), 1);
+    }
+
+    #[test]
+    fn set_new_section() {
+        let mut document = CfgDocument::parse("[Locations]\nname = Bot\r\n");
+        document.set("Bot Parameters", "path", Some("bot.exe"));
+
+        assert_eq!(document.to_string(), "[Locations]\r\nname = Bot\r\n\r\n[Bot Parameters]\r\npath = bot.exe\r\n");
+    }
+
+    #[test]
+    fn merge_removes_keys() {
+        let mut document = CfgDocument::parse(BOT_CFG);
+        document.merge(&conf(&[("Locations", "looks_config", Some("./appearance.cfg")), ("Locations", "name", Some("Bot"))]));
+
+        // `python_file` is the only line that should be gone, so the [Details] section is left exactly how it was
+        assert_eq!(document.to_string(), BOT_CFG.replace("python_file = ./bot.py\n", ""));
+    }
+
+    #[test]
+    fn merge_keeps_hash_comments() {
+        let text = "[Locations]\n# Path to loadout config\nlooks_config = ./appearance.cfg\n\n# Path to python file\npython_file = ./bot.py\n";
+
+        let mut document = CfgDocument::parse(text);
+        document.merge(&conf(&[
+            ("Locations", "looks_config", Some("./appearance.cfg")),
+            ("Locations", "python_file", Some("./bot.py")),
+        ]));
+        assert_eq!(document.to_string(), text);
+
+        let mut document = CfgDocument::parse(text);
+        document.merge(&conf(&[("Locations", "looks_config", Some("./other_appearance.cfg"))]));
+        assert_eq!(
+            document.to_string(),
+            "[Locations]\n# Path to loadout config\nlooks_config = ./other_appearance.cfg\n\n# Path to python file\n"
+        );
+    }
+
+    #[test]
+    fn merge_unchanged_sections() {
+        let mut document = CfgDocument::parse(BOT_CFG);
+        document.merge(&conf(&[
+            ("Details", "developer", Some("Someone")),
+            ("Details", "language", Some("C#")),
+            ("Details", "description", Some("A bot that does things,\nand then some more things\nand even more things")),
+            ("Details", "fun_fact", None),
+            ("Locations", "looks_config", Some("./appearance.cfg")),
+            ("Locations", "python_file", Some("./bot.py")),
+        ]));
+
+        // `name` is removed from the second [Locations] section, but everything else stays byte-identical
+        assert_eq!(document.to_string(), BOT_CFG.replace("name = Bot\n", ""));
+    }
+}
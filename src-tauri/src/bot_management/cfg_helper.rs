@@ -1,5 +1,10 @@
+use super::cfg_document::{is_line_comment, CfgDocument};
 use configparser::ini::Ini;
-use std::path::Path;
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
 use thiserror::Error;
 use tokio::fs as async_fs;
 
@@ -11,6 +16,11 @@ pub enum Error {
     Io(#[from] std::io::Error),
 }
 
+/// Removes `#` comment lines, which `configparser` would otherwise read as keys
+fn remove_line_comments(text: &str) -> String {
+    text.lines().filter(|line| !is_line_comment(line)).collect::<Vec<_>>().join("\n")
+}
+
 /// Load a CFG file synchronously, returns a description of any errors if unable to do so
 ///
 /// # Arguments
@@ -20,7 +30,7 @@ pub fn load_cfg_sync<T: AsRef<Path>>(path: T) -> Result<Ini, Error> {
     let mut conf = Ini::new();
     conf.set_multiline(true);
     conf.set_comment_symbols(&[';']);
-    conf.load(path).map_err(Error::Load)?;
+    conf.read(remove_line_comments(&fs::read_to_string(path)?)).map_err(Error::Load)?;
 
     Ok(conf)
 }
@@ -34,23 +44,51 @@ pub async fn load_cfg<T: AsRef<Path>>(path: T) -> Result<Ini, Error> {
     let mut conf = Ini::new();
     conf.set_multiline(true);
     conf.set_comment_symbols(&[';']);
-    conf.read(async_fs::read_to_string(path).await?).map_err(Error::Load)?;
+    conf.read(remove_line_comments(&async_fs::read_to_string(path).await?)).map_err(Error::Load)?;
 
     Ok(conf)
 }
 
+/// Parse the text of a CFG file as a document, or start an empty document if the file doesn't exist yet
+fn to_document(text: io::Result<String>) -> Result<CfgDocument, Error> {
+    match text {
+        Ok(text) => Ok(CfgDocument::parse(&text)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(CfgDocument::parse("")),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Save a CFG file synchronously, returns a description of any errors if unable to do so
+///
+/// Only the values in the file are changed, so comments, the order of keys, and sections that aren't in `conf` are kept
+///
+/// # Arguments
+///
+/// * `conf`: The CFG file that needs to be saved
+/// * `path`: Where to save the CFG file to
+pub fn save_cfg_sync<T: AsRef<Path>>(conf: &Ini, path: T) -> Result<(), Error> {
+    let mut document = to_document(fs::read_to_string(&path))?;
+    document.merge(conf);
+    fs::write(path, document.to_string())?;
+    Ok(())
+}
+
 /// Save a CFG file, returns a description of any errors if unable to do so
 ///
+/// Only the values in the file are changed, so comments, the order of keys, and sections that aren't in `conf` are kept
+///
 /// # Arguments
 ///
 /// * `conf`: The CFG file that needs to be saved
 /// * `path`: Where to save the CFG file to
 pub async fn save_cfg<T: AsRef<Path>>(conf: &Ini, path: T) -> Result<(), Error> {
-    async_fs::write(path, conf.writes()).await?;
+    let mut document = to_document(async_fs::read_to_string(&path).await)?;
+    document.merge(conf);
+    async_fs::write(path, document.to_string()).await?;
     Ok(())
 }
 
-/// Load, change a key, and save a cfg file. Returns a descripton of any errors if unable to do so
+/// Load, change a key, and save a cfg file without touching the rest of it. Returns a descripton of any errors if unable to do so
 ///
 /// # Arguments
 ///
@@ -59,7 +97,8 @@ pub async fn save_cfg<T: AsRef<Path>>(conf: &Ini, path: T) -> Result<(), Error>
 /// * `key`: The key in `section` to change
 /// * `value`: What to set the value to
 pub async fn change_key_in_cfg<T: AsRef<Path>>(path: T, section: &str, key: &str, value: String) -> Result<(), Error> {
-    let mut conf = load_cfg(&path).await?;
-    conf.set(section, key, Some(value));
-    save_cfg(&conf, path).await
+    let mut document = CfgDocument::parse(&async_fs::read_to_string(&path).await?);
+    document.set(section, key, Some(&value));
+    async_fs::write(path, document.to_string()).await?;
+    Ok(())
 }
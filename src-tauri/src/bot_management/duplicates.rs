@@ -0,0 +1,341 @@
+use crate::{bot_management::cfg_helper::save_cfg, ccprintln, get_config_path, load_gui_config, rlbot::parsing::bot_config_bundle::BotConfigBundle};
+use configparser::ini::Ini;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    fs::{metadata, File},
+    hash::{Hash, Hasher},
+    io::Read,
+    sync::Mutex,
+    time::UNIX_EPOCH,
+};
+use tauri::Window;
+
+const BOT_FOLDER_SETTINGS_HEADER: &str = "bot_folder_settings";
+const PREFERRED_COPIES_KEY: &str = "preferred_copies";
+
+/// How much of a file is read at a time when hashing it
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A file's path, size, and modification time
+type FileKey = (String, u64, u128);
+
+/// Hashes of files that were already read, so unchanged files are never read twice
+static FILE_HASHES: Lazy<Mutex<HashMap<FileKey, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// One copy of a bot that exists more than once
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateCopy {
+    pub bot: BotConfigBundle,
+    /// When any of the bot's files were last modified, in seconds since the Unix epoch
+    pub modified: u64,
+    /// A hash of the config file and the file that runs the bot, or None if they couldn't be read
+    pub content_hash: Option<String>,
+    /// If this is the most recently modified copy
+    pub newest: bool,
+    /// If the user picked this copy to be used when the bot is looked up by name
+    pub preferred: bool,
+}
+
+/// Copies of what is probably the same bot, newest first
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    pub name: String,
+    /// If every copy has the same name
+    pub same_name: bool,
+    /// If every copy has the same content
+    pub identical: bool,
+    pub copies: Vec<DuplicateCopy>,
+}
+
+/// The files that make up a bot: the config file and the file that runs it
+fn bot_files(bot: &BotConfigBundle) -> impl Iterator<Item = &String> {
+    [Some(&bot.path), Some(&bot.python_path).filter(|path| !path.is_empty()), bot.executable_path.as_ref()]
+        .into_iter()
+        .flatten()
+}
+
+fn modified_time(bot: &BotConfigBundle) -> u64 {
+    bot_files(bot)
+        .filter_map(|path| metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .filter_map(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Hash a file a chunk at a time, so big executables are never loaded into memory all at once
+fn file_hash(path: &str) -> Option<u64> {
+    let metadata = metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    let key = (path.to_owned(), metadata.len(), modified);
+
+    if let Some(&hash) = FILE_HASHES.lock().ok()?.get(&key) {
+        return Some(hash);
+    }
+
+    let mut file = File::open(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = vec![0; HASH_CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+    }
+
+    let hash = hasher.finish();
+    FILE_HASHES.lock().ok()?.insert(key, hash);
+
+    Some(hash)
+}
+
+fn content_hash(bot: &BotConfigBundle) -> Option<String> {
+    let mut hasher = DefaultHasher::new();
+    for path in bot_files(bot) {
+        file_hash(path)?.hash(&mut hasher);
+    }
+
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+/// Load the user's preferred copy of each duplicated bot, as a map of bot name to config path
+pub fn load_preferred_copies(conf: &Ini) -> HashMap<String, String> {
+    conf.get(BOT_FOLDER_SETTINGS_HEADER, PREFERRED_COPIES_KEY)
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default()
+}
+
+/// Set which copy of a bot is used when it's looked up by name
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `name` - The name of the bot
+/// * `config_path` - The config path of the copy to use, or None to go back to using the newest copy
+pub async fn set_preferred_copy(window: &Window, name: String, config_path: Option<String>) {
+    let mut conf = load_gui_config(window).await;
+    let mut preferred = load_preferred_copies(&conf);
+
+    match config_path {
+        Some(config_path) => preferred.insert(name, config_path),
+        None => preferred.remove(&name),
+    };
+
+    conf.set(BOT_FOLDER_SETTINGS_HEADER, PREFERRED_COPIES_KEY, serde_json::to_string(&preferred).ok());
+
+    if let Err(e) = save_cfg(&conf, get_config_path()).await {
+        ccprintln!(window, "Error saving preferred bot copies: {e}");
+    }
+}
+
+/// Picks which copy of a bot to use out of the bots that share a name
+///
+/// The user's preferred copy is used if it's one of the copies, otherwise the most recently modified copy is used
+pub fn pick_copy(copies: Vec<BotConfigBundle>, preferred: &HashMap<String, String>) -> Option<BotConfigBundle> {
+    if let Some(index) = copies.iter().position(|bot| preferred.get(&bot.name) == Some(&bot.path)) {
+        return copies.into_iter().nth(index);
+    }
+
+    copies.into_iter().max_by_key(modified_time)
+}
+
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+
+    index
+}
+
+/// Groups the bots that have the same name or the same content
+///
+/// # Arguments
+///
+/// * `bots` - Every bot that was found
+/// * `preferred` - The user's preferred copies, from `load_preferred_copies`
+pub fn find_duplicates(bots: Vec<BotConfigBundle>, preferred: &HashMap<String, String>) -> Vec<DuplicateGroup> {
+    let hashes = bots.iter().map(content_hash).collect::<Vec<_>>();
+    let mut parents = (0..bots.len()).collect::<Vec<_>>();
+
+    // bots with the same name or the same hash are joined into the same group
+    let mut first_by_name = HashMap::new();
+    let mut first_by_hash = HashMap::new();
+    for (index, bot) in bots.iter().enumerate() {
+        let mut firsts = vec![*first_by_name.entry(bot.name.trim().to_lowercase()).or_insert(index)];
+        if let Some(hash) = &hashes[index] {
+            firsts.push(*first_by_hash.entry(hash).or_insert(index));
+        }
+
+        for first in firsts {
+            let root = find_root(&mut parents, first);
+            let index_root = find_root(&mut parents, index);
+            parents[index_root] = root;
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<DuplicateCopy>> = BTreeMap::new();
+    for (index, (bot, content_hash)) in bots.into_iter().zip(hashes).enumerate() {
+        let root = find_root(&mut parents, index);
+        groups.entry(root).or_default().push(DuplicateCopy {
+            modified: modified_time(&bot),
+            preferred: preferred.get(&bot.name) == Some(&bot.path),
+            newest: false,
+            content_hash,
+            bot,
+        });
+    }
+
+    let mut groups = groups
+        .into_values()
+        .filter(|copies| copies.len() > 1)
+        .map(|mut copies| {
+            copies.sort_by_key(|copy| Reverse(copy.modified));
+
+            let newest = copies[0].modified;
+            for copy in &mut copies {
+                copy.newest = copy.modified == newest;
+            }
+
+            DuplicateGroup {
+                name: copies[0].bot.name.clone(),
+                same_name: copies.iter().all(|copy| copy.bot.name == copies[0].bot.name),
+                identical: copies.iter().all(|copy| copy.content_hash.is_some() && copy.content_hash == copies[0].content_hash),
+                copies,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    groups.sort_by_cached_key(|group| group.name.to_lowercase());
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        fs::{create_dir_all, remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    /// A folder of bot config files that is deleted when the test is done
+    struct TestFolder(PathBuf);
+
+    impl TestFolder {
+        fn new(test: &str) -> Self {
+            let folder = std::env::temp_dir().join(format!("rlbot-duplicates-{}-{test}", std::process::id()));
+            create_dir_all(&folder).unwrap();
+            Self(folder)
+        }
+
+        /// Writes a config file with the given contents and returns a bot that uses it
+        fn bot(&self, file: &str, name: &str, contents: &str) -> BotConfigBundle {
+            let path = self.0.join(file);
+            write(&path, contents).unwrap();
+            self.missing_bot(file, name)
+        }
+
+        /// Returns a bot whose config file doesn't exist
+        fn missing_bot(&self, file: &str, name: &str) -> BotConfigBundle {
+            BotConfigBundle {
+                name: name.to_owned(),
+                path: self.0.join(file).to_string_lossy().to_string(),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl Drop for TestFolder {
+        fn drop(&mut self) {
+            remove_dir_all(&self.0).ok();
+        }
+    }
+
+    fn group_paths(group: &DuplicateGroup) -> Vec<&str> {
+        let mut paths = group.copies.iter().map(|copy| copy.bot.path.as_str()).collect::<Vec<_>>();
+        paths.sort_unstable();
+        paths
+    }
+
+    #[test]
+    fn same_name_different_content() {
+        let folder = TestFolder::new("same-name");
+        let bots = vec![
+            folder.bot("a.cfg", "Bot", "[Locations]\nname = Bot\n"),
+            folder.bot("b.cfg", "Bot", "[Locations]\nname = Bot\n; v2\n"),
+        ];
+
+        let groups = find_duplicates(bots.clone(), &HashMap::new());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Bot");
+        assert!(groups[0].same_name);
+        assert!(!groups[0].identical);
+        assert_eq!(group_paths(&groups[0]), [bots[0].path.as_str(), bots[1].path.as_str()]);
+    }
+
+    #[test]
+    fn same_content_different_names() {
+        let folder = TestFolder::new("same-content");
+        let bots = vec![
+            folder.bot("a.cfg", "Alpha", "[Locations]\nname = Bot\n"),
+            folder.bot("b.cfg", "Beta", "[Locations]\nname = Bot\n"),
+            folder.bot("c.cfg", "Gamma", "[Locations]\nname = Gamma\n"),
+        ];
+
+        let groups = find_duplicates(bots.clone(), &HashMap::new());
+        assert_eq!(groups.len(), 1);
+        assert!(!groups[0].same_name);
+        assert!(groups[0].identical);
+        assert_eq!(group_paths(&groups[0]), [bots[0].path.as_str(), bots[1].path.as_str()]);
+    }
+
+    #[test]
+    fn transitive_merging() {
+        let folder = TestFolder::new("transitive");
+        // a & b share a name, b & c share content, so all three are one group even though a & c have nothing in common
+        let bots = vec![
+            folder.bot("a.cfg", "Alpha", "one"),
+            folder.bot("b.cfg", "alpha ", "two"),
+            folder.bot("c.cfg", "Gamma", "two"),
+            folder.bot("d.cfg", "Delta", "three"),
+        ];
+
+        let groups = find_duplicates(bots.clone(), &HashMap::new());
+        assert_eq!(groups.len(), 1);
+        assert!(!groups[0].same_name);
+        assert!(!groups[0].identical);
+        assert_eq!(group_paths(&groups[0]), [bots[0].path.as_str(), bots[1].path.as_str(), bots[2].path.as_str()]);
+    }
+
+    #[test]
+    fn preferred_copy() {
+        let folder = TestFolder::new("preferred");
+        // the copy that doesn't exist on disk is never the newest
+        let newest = folder.bot("new.cfg", "Bot", "new");
+        let oldest = folder.missing_bot("old.cfg", "Bot");
+        let copies = vec![oldest.clone(), newest.clone()];
+
+        assert_eq!(pick_copy(copies.clone(), &HashMap::new()), Some(newest.clone()));
+
+        let preferred = HashMap::from([("Bot".to_owned(), oldest.path.clone())]);
+        assert_eq!(pick_copy(copies.clone(), &preferred), Some(oldest.clone()));
+
+        let groups = find_duplicates(copies.clone(), &preferred);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].copies[0].bot, newest);
+        assert!(groups[0].copies[0].newest && !groups[0].copies[0].preferred);
+        assert!(!groups[0].copies[1].newest && groups[0].copies[1].preferred);
+
+        // a preferred copy that was deleted or moved falls back to the newest copy
+        let gone = HashMap::from([("Bot".to_owned(), folder.0.join("gone.cfg").to_string_lossy().to_string())]);
+        assert_eq!(pick_copy(copies.clone(), &gone), Some(newest));
+        assert!(find_duplicates(copies, &gone)[0].copies.iter().all(|copy| !copy.preferred));
+
+        assert_eq!(pick_copy(Vec::new(), &preferred), None);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    ccprintln,
+    rlbot::parsing::{bot_config_bundle::BotConfigBundle, bot_index},
+    settings::BotFolders,
+};
+use notify_debouncer_mini::{
+    new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer,
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use tauri::{async_runtime::block_on as tauri_block_on, Window};
+
+pub const BOTS_CHANGED_SIGNAL: &str = "bots-changed";
+
+/// How long the files have to stop changing before the bots are rescanned, so saving or extracting many files only causes one rescan
+const DEBOUNCE_TIME: Duration = Duration::from_millis(750);
+
+static FOLDER_WATCHER: Mutex<Option<Debouncer<RecommendedWatcher>>> = Mutex::new(None);
+
+/// Counts calls to `watch_bot_folders`, so a watcher that finishes starting after a newer one doesn't replace it
+static WATCHER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// The payload of the `bots-changed` signal
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct BotsChanged {
+    pub added: Vec<BotConfigBundle>,
+    pub removed: Vec<BotConfigBundle>,
+    pub modified: Vec<BotConfigBundle>,
+}
+
+impl BotsChanged {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Checks if a changed path could change the list of bots
+///
+/// Besides config files, this includes folders that were created or moved in, because moving a whole bot into a watched folder only reports the folder,
+/// and deleted folders that had known bots in them
+fn is_relevant(path: &Path, known: &HashMap<String, BotConfigBundle>) -> bool {
+    if path.extension().map_or(false, |extension| extension == "cfg") {
+        return true;
+    }
+
+    path.is_dir() || (!path.exists() && known.keys().any(|bot_path| Path::new(bot_path).starts_with(path)))
+}
+
+/// Compares the bots from a rescan to the previously known bots, replacing the known bots with the new ones
+fn diff_bots(known: &mut HashMap<String, BotConfigBundle>, bots: Vec<BotConfigBundle>) -> BotsChanged {
+    let current = bots.into_iter().map(|bot| (bot.path.clone(), bot)).collect::<HashMap<_, _>>();
+    let mut changes = BotsChanged::default();
+
+    for (path, bot) in &current {
+        match known.get(path) {
+            None => changes.added.push(bot.clone()),
+            Some(old_bot) if old_bot != bot => changes.modified.push(bot.clone()),
+            Some(_) => {}
+        }
+    }
+
+    changes.removed = known.drain().filter(|(path, _)| !current.contains_key(path)).map(|(_, bot)| bot).collect();
+    *known = current;
+
+    changes
+}
+
+fn rescan(window: &Window, bot_folders: &BotFolders, known: &Mutex<HashMap<String, BotConfigBundle>>) {
+    let bots = tauri_block_on(bot_index::scan_bots(window, bot_folders));
+
+    let changes = match known.lock() {
+        Ok(mut known) => diff_bots(&mut known, bots),
+        Err(_) => {
+            ccprintln(window, "Known bots mutex was poisoned");
+            return;
+        }
+    };
+
+    if changes.is_empty() {
+        return;
+    }
+
+    if let Err(e) = window.emit(BOTS_CHANGED_SIGNAL, changes) {
+        ccprintln!(window, "Error emitting {BOTS_CHANGED_SIGNAL}: {e}");
+    }
+}
+
+/// Starts watching the visible bot folders and files for changes, replacing any previous watcher
+///
+/// When a config file is added, removed, or modified, the bots are rescanned and the differences are sent to the GUI with the `bots-changed` signal
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `bot_folders` - The bot folders and files to watch, only the visible ones are watched
+pub fn watch_bot_folders(window: &Window, bot_folders: &BotFolders) {
+    let generation = WATCHER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    // the old watcher is stopped right away, so it doesn't report changes to folders that aren't visible anymore
+    if let Ok(mut folder_watcher) = FOLDER_WATCHER.lock() {
+        *folder_watcher = None;
+    }
+
+    let window = window.clone();
+    let bot_folders = bot_folders.clone();
+    tauri::async_runtime::spawn(async move {
+        // the bots are remembered before watching starts, so the first change is compared to them instead of reporting every bot as added
+        let mut known = HashMap::new();
+        diff_bots(&mut known, bot_index::scan_bots(&window, &bot_folders).await);
+
+        start_watcher(&window, &bot_folders, known, generation);
+    });
+}
+
+/// Starts the watcher for a call to `watch_bot_folders`, unless `watch_bot_folders` was called again while the bots were being scanned
+fn start_watcher(window: &Window, bot_folders: &BotFolders, known: HashMap<String, BotConfigBundle>, generation: u64) {
+    if WATCHER_GENERATION.load(Ordering::SeqCst) != generation {
+        return;
+    }
+
+    let known = Mutex::new(known);
+
+    let event_window = window.clone();
+    let event_bot_folders = bot_folders.clone();
+
+    let debouncer = new_debouncer(DEBOUNCE_TIME, None, move |result: DebounceEventResult| match result {
+        Ok(events) => {
+            let relevant = match known.lock() {
+                Ok(known) => events.iter().any(|event| is_relevant(&event.path, &known)),
+                Err(_) => {
+                    ccprintln(&event_window, "Known bots mutex was poisoned");
+                    return;
+                }
+            };
+
+            if relevant {
+                rescan(&event_window, &event_bot_folders, &known);
+            }
+        }
+        Err(errors) => {
+            for e in errors {
+                ccprintln!(&event_window, "Error watching bot folders: {e}");
+            }
+        }
+    });
+
+    let mut debouncer = match debouncer {
+        Ok(debouncer) => debouncer,
+        Err(e) => {
+            ccprintln!(window, "Error starting the bot folder watcher: {e}");
+            return;
+        }
+    };
+
+    let watcher = debouncer.watcher();
+
+    for (path, _) in bot_folders.folders.iter().filter(|(_, props)| props.visible) {
+        if let Err(e) = watcher.watch(Path::new(path), RecursiveMode::Recursive) {
+            ccprintln!(window, "Error watching {path}: {e}");
+        }
+    }
+
+    // editors often replace a file instead of writing to it, so the folder around the file is watched instead of the file itself
+    for (path, _) in bot_folders.files.iter().filter(|(_, props)| props.visible) {
+        if let Some(parent) = Path::new(path).parent() {
+            if let Err(e) = watcher.watch(parent, RecursiveMode::NonRecursive) {
+                ccprintln!(window, "Error watching {path}: {e}");
+            }
+        }
+    }
+
+    match FOLDER_WATCHER.lock() {
+        // checked again while holding the lock, in case a newer watcher was started while this one was being set up
+        Ok(mut folder_watcher) if WATCHER_GENERATION.load(Ordering::SeqCst) == generation => *folder_watcher = Some(debouncer),
+        Ok(_) => {}
+        Err(_) => ccprintln(window, "FOLDER_WATCHER mutex was poisoned"),
+    }
+}
@@ -0,0 +1,365 @@
+use crate::rlbot::parsing::{
+    agent_config_parser::{BotLooksConfig, BotTeamLooksConfig},
+    item_catalog::{Item, ItemSlot, COLOR_PALETTES, ITEM_CATALOG},
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// Paint IDs go from 0 (no paint) to 13 (saffron)
+const PAINT_COUNT: u32 = 14;
+
+/// Black, sky blue, cobalt, grey, and titanium white
+const BLUE_PAINTS: [u32; 5] = [3, 4, 5, 11, 12];
+
+/// Crimson, black, burnt sienna, orange, grey, titanium white, and saffron
+const ORANGE_PAINTS: [u32; 7] = [1, 3, 6, 10, 11, 12, 13];
+
+const BLUE_HUES: RangeInclusive<f32> = 180.0..=250.0;
+const ORANGE_HUES: RangeInclusive<f32> = 0.0..=50.0;
+
+/// How likely it is that the decal, antenna, and topper are left empty, so not every car is covered in items
+const EMPTY_CHANCE: f64 = 0.25;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadoutStyle {
+    /// Any item, paint, and color
+    #[default]
+    Random,
+    /// Colors and paints that match the team's color, or are neutral
+    TeamColors,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LoadoutOptions {
+    pub style: LoadoutStyle,
+    /// Only use car bodies with one of these names, like "Octane", or any body if empty
+    pub bodies: Vec<String>,
+    /// Only use decals that are made for the car's body, which leaves out the animated decals that fit every car
+    pub no_animated_decals: bool,
+    /// Leave every item unpainted
+    pub no_paint: bool,
+    /// The same seed and options always give the same loadout, a random seed is picked if this is None
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedLoadout {
+    /// The seed that was used, so the loadout can be made again
+    pub seed: u64,
+    pub looks: BotLooksConfig,
+}
+
+#[derive(Debug, Error)]
+pub enum LoadoutGeneratorError {
+    #[error("No car bodies are named {0}")]
+    NoBodies(String),
+}
+
+#[derive(Clone, Copy)]
+enum Team {
+    Blue,
+    Orange,
+}
+
+/// Get the hue of a color in degrees and it's saturation, from 0 to 1
+fn hue_and_saturation([r, g, b]: [u8; 3]) -> (f32, f32) {
+    let (r, g, b) = (f32::from(r) / 255., f32::from(g) / 255., f32::from(b) / 255.);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    if delta == 0. {
+        return (0., 0.);
+    }
+
+    let hue = if max == r {
+        60. * ((g - b) / delta).rem_euclid(6.)
+    } else if max == g {
+        60. * ((b - r) / delta + 2.)
+    } else {
+        60. * ((r - g) / delta + 4.)
+    };
+
+    (hue, delta / max)
+}
+
+/// Get the indexes of the colors that match the given hues, optionally including colors with no hue at all
+fn matching_colors(colors: &[[u8; 3]], hues: &RangeInclusive<f32>, include_neutral: bool) -> Vec<usize> {
+    colors
+        .iter()
+        .enumerate()
+        .filter(|(_, &color)| {
+            let (hue, saturation) = hue_and_saturation(color);
+            (include_neutral && saturation == 0.) || (saturation > 0. && hues.contains(&hue))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn pick_id<'a>(rng: &mut StdRng, items: impl Iterator<Item = &'a Item>) -> String {
+    items.collect::<Vec<_>>().choose(rng).map(|item| item.id.to_string()).unwrap_or_default()
+}
+
+fn pick_optional_id<'a>(rng: &mut StdRng, items: impl Iterator<Item = &'a Item>) -> String {
+    if rng.gen_bool(EMPTY_CHANCE) {
+        "0".to_owned()
+    } else {
+        pick_id(rng, items)
+    }
+}
+
+fn generate_team(rng: &mut StdRng, team: Team, options: &LoadoutOptions, bodies: &[&Item]) -> BotTeamLooksConfig {
+    let colors = &*COLOR_PALETTES;
+    let (team_colors, hues, team_paints) = match team {
+        Team::Blue => (&colors.blue, BLUE_HUES, &BLUE_PAINTS[..]),
+        Team::Orange => (&colors.orange, ORANGE_HUES, &ORANGE_PAINTS[..]),
+    };
+
+    let (team_color_id, custom_color_id) = match options.style {
+        LoadoutStyle::Random => (rng.gen_range(0..team_colors.len().max(1)), rng.gen_range(0..colors.secondary.len().max(1))),
+        LoadoutStyle::TeamColors => (
+            matching_colors(team_colors, &hues, false).choose(rng).copied().unwrap_or_default(),
+            matching_colors(&colors.secondary, &hues, true).choose(rng).copied().unwrap_or_default(),
+        ),
+    };
+
+    let mut paint = || {
+        if options.no_paint {
+            0
+        } else if options.style == LoadoutStyle::TeamColors {
+            team_paints.choose(rng).copied().unwrap_or_default()
+        } else {
+            rng.gen_range(0..PAINT_COUNT)
+        }
+        .to_string()
+    };
+
+    let [car_paint_id, decal_paint_id, wheels_paint_id, boost_paint_id, antenna_paint_id, hat_paint_id, trails_paint_id, goal_explosion_paint_id] =
+        [paint(), paint(), paint(), paint(), paint(), paint(), paint(), paint()];
+
+    let body = bodies.choose(rng).copied();
+
+    // decals for a specific body are named like "Octane: Flames", while the animated decals that fit every body have no prefix
+    let decal_prefix = body.map(|body| format!("{}: ", body.name)).unwrap_or_default();
+    let decals = ITEM_CATALOG.in_slot(ItemSlot::Skin).filter(|decal| {
+        if decal.name.contains(": ") {
+            !decal_prefix.is_empty() && decal.name.starts_with(&decal_prefix)
+        } else {
+            !options.no_animated_decals
+        }
+    });
+
+    BotTeamLooksConfig {
+        team_color_id: team_color_id.to_string(),
+        custom_color_id: custom_color_id.to_string(),
+        car_id: body.map(|body| body.id.to_string()).unwrap_or_default(),
+        decal_id: pick_optional_id(rng, decals),
+        wheels_id: pick_id(rng, ITEM_CATALOG.in_slot(ItemSlot::Wheels)),
+        boost_id: pick_id(rng, ITEM_CATALOG.in_slot(ItemSlot::Boost)),
+        antenna_id: pick_optional_id(rng, ITEM_CATALOG.in_slot(ItemSlot::Antenna)),
+        hat_id: pick_optional_id(rng, ITEM_CATALOG.in_slot(ItemSlot::Hat)),
+        paint_finish_id: pick_id(rng, ITEM_CATALOG.in_slot(ItemSlot::PaintFinish)),
+        custom_finish_id: pick_id(rng, ITEM_CATALOG.in_slot(ItemSlot::PaintFinish)),
+        engine_audio_id: pick_id(rng, ITEM_CATALOG.in_slot(ItemSlot::EngineAudio)),
+        trails_id: pick_id(rng, ITEM_CATALOG.in_slot(ItemSlot::SupersonicTrail)),
+        goal_explosion_id: pick_id(rng, ITEM_CATALOG.in_slot(ItemSlot::GoalExplosion)),
+        car_paint_id,
+        decal_paint_id,
+        wheels_paint_id,
+        boost_paint_id,
+        antenna_paint_id,
+        hat_paint_id,
+        trails_paint_id,
+        goal_explosion_paint_id,
+        ..Default::default()
+    }
+}
+
+/// Generates a loadout for both teams out of the items in the item catalog
+///
+/// # Arguments
+///
+/// * `options` - The style of the loadout and the constraints on what items can be used
+pub fn generate(options: &LoadoutOptions) -> Result<GeneratedLoadout, LoadoutGeneratorError> {
+    let bodies = if options.bodies.is_empty() {
+        ITEM_CATALOG.in_slot(ItemSlot::Body).collect::<Vec<_>>()
+    } else {
+        options.bodies.iter().flat_map(|name| ITEM_CATALOG.by_name(ItemSlot::Body, name)).collect()
+    };
+
+    if bodies.is_empty() {
+        return Err(LoadoutGeneratorError::NoBodies(options.bodies.join(" or ")));
+    }
+
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    Ok(GeneratedLoadout {
+        seed,
+        looks: BotLooksConfig {
+            blue: generate_team(&mut rng, Team::Blue, options, &bodies),
+            orange: generate_team(&mut rng, Team::Orange, options, &bodies),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEEDS: RangeInclusive<u64> = 0..=50;
+
+    fn options(seed: u64) -> LoadoutOptions {
+        LoadoutOptions {
+            seed: Some(seed),
+            ..Default::default()
+        }
+    }
+
+    fn teams(looks: &BotLooksConfig) -> [&BotTeamLooksConfig; 2] {
+        [&looks.blue, &looks.orange]
+    }
+
+    fn paints(team: &BotTeamLooksConfig) -> [&str; 8] {
+        [
+            &team.car_paint_id,
+            &team.decal_paint_id,
+            &team.wheels_paint_id,
+            &team.boost_paint_id,
+            &team.antenna_paint_id,
+            &team.hat_paint_id,
+            &team.trails_paint_id,
+            &team.goal_explosion_paint_id,
+        ]
+        .map(String::as_str)
+    }
+
+    #[test]
+    fn same_seed_same_loadout() {
+        for style in [LoadoutStyle::Random, LoadoutStyle::TeamColors] {
+            let options = LoadoutOptions { style, ..options(1234) };
+            let first = generate(&options).unwrap();
+            let second = generate(&options).unwrap();
+
+            assert_eq!(first.seed, 1234);
+            assert_eq!(first.looks, second.looks);
+        }
+
+        assert_ne!(generate(&options(1)).unwrap().looks, generate(&options(2)).unwrap().looks);
+
+        // without a seed, the one that was picked makes the same loadout again
+        let random = generate(&LoadoutOptions::default()).unwrap();
+        assert_eq!(generate(&options(random.seed)).unwrap().looks, random.looks);
+    }
+
+    #[test]
+    fn loadouts_are_valid() {
+        for seed in SEEDS {
+            for style in [LoadoutStyle::Random, LoadoutStyle::TeamColors] {
+                let loadout = generate(&LoadoutOptions { style, ..options(seed) }).unwrap();
+                if let Err(e) = ITEM_CATALOG.validate(&loadout.looks) {
+                    panic!("Seed {seed} with {style:?} made an invalid loadout: {e}");
+                }
+
+                for team in teams(&loadout.looks) {
+                    assert!(!team.car_id.is_empty() && team.car_id != "0");
+                    assert!(paints(team).iter().all(|paint| paint.parse::<u32>().map_or(false, |paint| paint < PAINT_COUNT)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bodies() {
+        let bodies = ["Octane".to_owned(), "dominus".to_owned()];
+        let body_ids = bodies
+            .iter()
+            .flat_map(|name| ITEM_CATALOG.by_name(ItemSlot::Body, name))
+            .map(|item| item.id.to_string())
+            .collect::<Vec<_>>();
+        assert!(!body_ids.is_empty());
+
+        for seed in SEEDS {
+            let loadout = generate(&LoadoutOptions {
+                bodies: bodies.to_vec(),
+                ..options(seed)
+            })
+            .unwrap();
+
+            for team in teams(&loadout.looks) {
+                assert!(body_ids.contains(&team.car_id), "Seed {seed} picked body {}", team.car_id);
+            }
+        }
+
+        let error = generate(&LoadoutOptions {
+            bodies: vec!["Not A Car".to_owned()],
+            ..options(0)
+        })
+        .unwrap_err();
+        assert!(matches!(error, LoadoutGeneratorError::NoBodies(ref names) if names == "Not A Car"));
+    }
+
+    #[test]
+    fn no_animated_decals() {
+        for seed in SEEDS {
+            let loadout = generate(&LoadoutOptions {
+                no_animated_decals: true,
+                ..options(seed)
+            })
+            .unwrap();
+
+            for team in teams(&loadout.looks) {
+                // some bodies don't have any decals made for them
+                if team.decal_id.is_empty() || team.decal_id == "0" {
+                    continue;
+                }
+
+                let body = ITEM_CATALOG.by_id(team.car_id.parse().unwrap()).unwrap();
+                let decal = ITEM_CATALOG.by_id(team.decal_id.parse().unwrap()).unwrap();
+                assert!(
+                    decal.name.starts_with(&format!("{}: ", body.name)),
+                    "Seed {seed} picked decal {} for {}",
+                    decal.name,
+                    body.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn no_paint() {
+        for seed in SEEDS {
+            for style in [LoadoutStyle::Random, LoadoutStyle::TeamColors] {
+                let loadout = generate(&LoadoutOptions {
+                    style,
+                    no_paint: true,
+                    ..options(seed)
+                })
+                .unwrap();
+
+                for team in teams(&loadout.looks) {
+                    assert_eq!(paints(team), ["0"; 8], "Seed {seed} with {style:?} painted an item");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn team_colors() {
+        for seed in SEEDS {
+            let loadout = generate(&LoadoutOptions {
+                style: LoadoutStyle::TeamColors,
+                ..options(seed)
+            })
+            .unwrap();
+
+            for (team, allowed) in [(&loadout.looks.blue, &BLUE_PAINTS[..]), (&loadout.looks.orange, &ORANGE_PAINTS[..])] {
+                assert!(paints(team).iter().all(|paint| allowed.contains(&paint.parse().unwrap())));
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use crate::get_content_folder;
+use image::{io::Reader as ImageReader, ImageFormat};
+use serde::Deserialize;
+use std::{
+    collections::hash_map::DefaultHasher,
+    error::Error as StdError,
+    fs::{create_dir_all, metadata, read, read_dir, remove_file, rename},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::UNIX_EPOCH,
+};
+use tauri::http::{Request, Response, ResponseBuilder};
+use thiserror::Error;
+
+/// The URI scheme that cached thumbnails are served from
+pub const LOGO_PROTOCOL: &str = "logo";
+
+// Windows' webview only allows custom protocols through a special https domain
+#[cfg(windows)]
+const LOGO_URL_PREFIX: &str = "https://logo.localhost/";
+#[cfg(not(windows))]
+const LOGO_URL_PREFIX: &str = "logo://localhost/";
+
+/// When the cache has more thumbnails than this, the oldest ones are deleted on startup
+///
+/// Enough for both sizes of every bot in the botpack and then some, so only thumbnails of bots that are long gone are usually deleted
+const MAX_THUMBNAILS: usize = 2000;
+
+/// Makes the names of temporary files unique within this process
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogoSize {
+    /// For bot and script cards
+    #[default]
+    Small,
+    /// For the bot info dialog
+    Large,
+}
+
+impl LogoSize {
+    /// Thumbnails are scaled down to fit in a square of this many pixels
+    const fn pixels(self) -> u32 {
+        match self {
+            Self::Small => 64,
+            Self::Large => 256,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LogoCacheError {
+    #[error("I/O error when caching a logo: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Couldn't convert a logo: {0}")]
+    Image(#[from] image::ImageError),
+}
+
+fn get_cache_folder() -> PathBuf {
+    get_content_folder().join("logo_cache")
+}
+
+fn hash<T: Hash>(value: T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Get the start of the file names of every thumbnail of a logo, no matter what version of the logo it was made from
+fn thumbnail_prefix(path: &Path) -> String {
+    format!("{:016x}_", hash(path))
+}
+
+/// Get the file name of a logo's thumbnail in the cache, which changes whenever the logo does, or None if the logo doesn't exist
+fn thumbnail_name(path: &Path, size: LogoSize) -> Option<String> {
+    let metadata = metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+
+    Some(format!("{}{:016x}_{}.png", thumbnail_prefix(path), hash((modified, metadata.len())), size.pixels()))
+}
+
+/// Deletes the thumbnails that were made from older versions of a logo
+fn remove_old_thumbnails(path: &Path, size: LogoSize, current_name: &str) {
+    let prefix = thumbnail_prefix(path);
+    let suffix = format!("_{}.png", size.pixels());
+
+    let Ok(entries) = read_dir(get_cache_folder()) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(&prefix) && name.ends_with(&suffix) && name != current_name {
+            // another thread could be removing it at the same time, or the GUI could still have it open
+            let _ = remove_file(entry.path());
+        }
+    }
+}
+
+/// Makes a PNG thumbnail of a logo, or reuses the cached one if the logo hasn't changed since it was made
+fn make_thumbnail(path: &Path, size: LogoSize) -> Result<Option<String>, LogoCacheError> {
+    let Some(name) = thumbnail_name(path, size) else {
+        return Ok(None);
+    };
+
+    let cache_path = get_cache_folder().join(&name);
+    if !cache_path.exists() {
+        let logo = ImageReader::open(path)?.with_guessed_format()?.decode()?;
+
+        // logos that are already small enough are only converted, not scaled up
+        let pixels = size.pixels();
+        let thumbnail = if logo.width() > pixels || logo.height() > pixels {
+            logo.thumbnail(pixels, pixels)
+        } else {
+            logo
+        };
+
+        // write to a temporary file first so a thumbnail that's being made is never served half-written
+        // the name is unique because the same logo can be converted on multiple threads at once
+        create_dir_all(get_cache_folder())?;
+        let temp_path = get_cache_folder().join(format!("{name}.{}.{}.part", process::id(), TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)));
+        thumbnail.save_with_format(&temp_path, ImageFormat::Png)?;
+
+        if let Err(e) = rename(&temp_path, &cache_path) {
+            let _ = remove_file(&temp_path);
+
+            // another thread finishing the same thumbnail first is fine, because it made the same image
+            if !cache_path.exists() {
+                return Err(e.into());
+            }
+        }
+
+        remove_old_thumbnails(path, size, &name);
+    }
+
+    Ok(Some(format!("{LOGO_URL_PREFIX}{name}")))
+}
+
+/// Deletes temporary files that were left behind, and the oldest thumbnails if there are more than `MAX_THUMBNAILS`
+///
+/// Should be called before any thumbnails are made, so a thumbnail that the GUI is showing isn't deleted
+pub fn prune() -> Result<(), LogoCacheError> {
+    let cache_folder = get_cache_folder();
+    if !cache_folder.exists() {
+        return Ok(());
+    }
+
+    let mut thumbnails = Vec::new();
+
+    for entry in read_dir(cache_folder)?.flatten() {
+        let path = entry.path();
+
+        if path.extension().map_or(false, |extension| extension == "part") {
+            remove_file(&path)?;
+        } else if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) {
+            thumbnails.push((modified, path));
+        }
+    }
+
+    if thumbnails.len() > MAX_THUMBNAILS {
+        thumbnails.sort_unstable();
+
+        for (_, path) in thumbnails.drain(..thumbnails.len() - MAX_THUMBNAILS) {
+            remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Get a URL to a thumbnail of a logo, which can be used as the `src` of an image in the GUI
+///
+/// Returns `None` if there's no logo or it can't be converted, so the GUI shows no logo
+///
+/// # Arguments
+///
+/// * `path` - The path to the logo file
+/// * `size` - Which size of thumbnail to get
+pub fn thumbnail_url(path: &str, size: LogoSize) -> Option<String> {
+    make_thumbnail(Path::new(path), size).unwrap_or_else(|e| {
+        println!("Couldn't make a thumbnail of {path}: {e}");
+        None
+    })
+}
+
+/// Serves thumbnails from the cache for the `logo` URI scheme
+pub fn serve(request: &Request) -> Result<Response, Box<dyn StdError>> {
+    let name = request.uri().rsplit('/').next().unwrap_or_default();
+
+    // only names that `thumbnail_name` could have made are served, so nothing outside of the cache can be read
+    let is_thumbnail = name
+        .strip_suffix(".png")
+        .map_or(false, |stem| !stem.is_empty() && stem.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+
+    match is_thumbnail.then(|| read(get_cache_folder().join(name)).ok()).flatten() {
+        Some(bytes) => ResponseBuilder::new()
+            .mimetype("image/png")
+            // the name changes whenever the logo does, so the webview never has to check for a newer version
+            .header("Cache-Control", "max-age=31536000, immutable")
+            .body(bytes),
+        None => ResponseBuilder::new().status(404).body(Vec::new()),
+    }
+}
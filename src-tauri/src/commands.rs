@@ -2,14 +2,33 @@ use crate::{
     bot_management::{
         bot_creation::{bootstrap_python_bot, bootstrap_python_hivemind, bootstrap_rust_bot, bootstrap_scratch_bot, BoostrapError, CREATED_BOTS_FOLDER},
         downloader::{self, get_current_tag_name, ProgressBarUpdate},
+        logo_cache::{self, LogoSize},
         zip_extract_fixed::{self, ExtractError},
     },
+    config_handles::{scan_for_bots, scan_for_scripts},
+    console::{
+        builtins, completion, history,
+        jobs::{self, ConsoleJobInfo},
+    },
+    python::{
+        conflicts::{self, ConflictReport, RequirementSource},
+        doctor::{self, DoctorCheck},
+        interpreters::{interpreter_for, load_bot_interpreters},
+        packages,
+        requirements::{parse_requirements_file, Requirement},
+        site_packages::InstalledPackages,
+        snapshots::{self, PackageChange, PythonTransactionSummary},
+        standalone::{self, StandalonePythonError},
+        venvs::{self, BotVenv, BotVenvRegistry},
+        wheelhouse,
+    },
     rlbot::{
         agents::runnable::Runnable,
-        gateway_util,
+        gateway_util, loadout_generator,
         parsing::{
             agent_config_parser::BotLooksConfig,
             bot_config_bundle::{BotConfigBundle, RLBotCfgParseError, ScriptConfigBundle},
+            bot_index,
             match_settings_config_parser::{BoostAmount, GameMode, MaxScore, Rumble},
         },
         setup_manager,
@@ -18,10 +37,10 @@ use crate::{
     stories::cmaps::{Bot, BotType, Challenge, City, Script},
     *,
 };
-use futures_util::StreamExt;
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use futures_util::{future::join_all, StreamExt};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap, HashSet},
     error::Error,
     fs::{create_dir_all, File},
     io::{copy, Cursor, Write},
@@ -58,6 +77,17 @@ pub async fn check_rlbot_python() -> Result<HashMap<String, bool>, String> {
     Ok(dbg!(python_support))
 }
 
+/// Runs a checklist against the current Python, returning pass/warn/fail results with suggested fixes
+#[tauri::command]
+pub async fn python_doctor(window: Window) -> Result<Vec<DoctorCheck>, String> {
+    let needs_tkinter =
+        scan_for_bots(window.clone()).await?.iter().any(Runnable::requires_tkinter) || scan_for_scripts(window.clone()).await?.iter().any(Runnable::requires_tkinter);
+    let core_packages = packages::load_core_packages(&load_gui_config(&window).await);
+    let python_path = PYTHON_PATH.read().await.to_owned();
+
+    Ok(doctor::diagnose(&python_path, needs_tkinter, &core_packages))
+}
+
 fn ensure_bot_directory(window: &Window) -> PathBuf {
     let bot_directory_path = get_content_folder().join(CREATED_BOTS_FOLDER);
 
@@ -134,29 +164,14 @@ pub async fn begin_scratch_bot(window: Window, bot_name: String) -> Result<BotCo
     })
 }
 
-const PACKAGES: [&str; 9] = [
-    "pip",
-    "setuptools",
-    "wheel",
-    "numpy<1.23",
-    "scipy",
-    "numba<0.56",
-    "selenium",
-    "rlbot==1.*",
-    "rlbot_smh==1.*",
-];
-
-/// Apply version constraints to the given package name.
-fn get_package_name(package_name: &str) -> &str {
-    PACKAGES.into_iter().find(|package| package.contains(package_name)).unwrap_or(package_name)
-}
-
 #[tauri::command]
-pub async fn install_package(package_string: String) -> Result<PackageResult, String> {
-    let exit_code = spawn_capture_process_and_get_exit_code(
-        &*PYTHON_PATH.read().await,
-        ["-m", "pip", "install", "-U", "--no-warn-script-location", get_package_name(&package_string)],
-    );
+pub async fn install_package(window: Window, package_string: String) -> Result<PackageResult, String> {
+    let online = wheelhouse::is_online().await;
+    let python = PYTHON_PATH.read().await.to_owned();
+    let package = packages::get_package_name(&packages::load_core_packages(&load_gui_config(&window).await), &package_string);
+    let exit_code = snapshots::record(&window, &python, format!("install {package}"), || {
+        wheelhouse::pip_install(&window, &python, &[package.as_str()], true, online)
+    });
 
     Ok(PackageResult::new(exit_code, vec![package_string]))
 }
@@ -173,9 +188,20 @@ pub async fn install_requirements(window: Window, config_path: String) -> Result
         let bundle = BotConfigBundle::minimal_from_path(Path::new(&config_path)).await?;
 
         Ok(if let Some(file) = bundle.get_requirements_file() {
-            let python = PYTHON_PATH.read().await;
-            let packages = bundle.get_missing_packages(window, &*python);
-            let exit_code = spawn_capture_process_and_get_exit_code(&*python, ["-m", "pip", "install", "--no-warn-script-location", "-r", file]);
+            let global_python = PYTHON_PATH.read().await;
+            let pins = load_bot_interpreters(&load_gui_config(window).await);
+            let python = interpreter_for(&pins, &config_path, &global_python);
+            let packages = match InstalledPackages::query(python) {
+                Ok(installed) => bundle.get_missing_packages(window, &installed),
+                Err(e) => {
+                    ccprintln!(window, "Failed to calculate missing packages: {e}");
+                    Vec::new()
+                }
+            };
+            let online = wheelhouse::is_online().await;
+            let exit_code = snapshots::record(window, python, format!("install -r {file}"), || {
+                wheelhouse::pip_install(window, python, &["-r", file], false, online)
+            });
 
             PackageResult::new(exit_code, packages)
         } else {
@@ -192,12 +218,14 @@ pub async fn install_requirements(window: Window, config_path: String) -> Result
 
 #[tauri::command]
 pub async fn install_basic_packages(window: Window) -> Result<PackageResult, String> {
-    let packages = PACKAGES.iter().map(ToString::to_string).collect::<Vec<String>>();
+    let packages = packages::load_core_packages(&load_gui_config(&window).await);
+
+    let online = wheelhouse::is_online().await;
 
-    if matches!(online::tokio::check(None).await, Err(_)) {
+    if !online && !wheelhouse::has_wheels() {
         ccprintln(
             &window,
-            "Error connecting to the internet to install/update basic packages. Please check your internet connection and try again.",
+            "Error connecting to the internet to install/update basic packages, and there are no saved wheels to install from. Please check your internet connection and try again.",
         );
 
         return Ok(PackageResult::new(3, packages));
@@ -209,8 +237,8 @@ pub async fn install_basic_packages(window: Window) -> Result<PackageResult, Str
 
     let mut exit_code = 0;
 
-    for package in PACKAGES {
-        exit_code = spawn_capture_process_and_get_exit_code(&python, ["-m", "pip", "install", "-U", "--no-warn-script-location", package]);
+    for package in &packages {
+        exit_code = wheelhouse::pip_install(&window, &python, &[package.as_str()], true, online);
 
         if exit_code != 0 {
             break;
@@ -220,18 +248,119 @@ pub async fn install_basic_packages(window: Window) -> Result<PackageResult, Str
     Ok(PackageResult::new(exit_code, packages))
 }
 
+#[tauri::command]
+pub fn export_wheelhouse(window: Window, path: String) -> Result<usize, String> {
+    wheelhouse::export_wheelhouse(&path).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub fn import_wheelhouse(window: Window, path: String) -> Result<usize, String> {
+    wheelhouse::import_wheelhouse(&path).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub fn get_python_transactions(window: Window) -> Result<Vec<PythonTransactionSummary>, String> {
+    snapshots::list_transactions().map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub fn get_python_transaction_diff(window: Window, id: u32) -> Result<Vec<PackageChange>, String> {
+    snapshots::diff_transaction(id).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn rollback_python_transaction(window: Window, id: u32) -> Result<i32, String> {
+    let online = wheelhouse::is_online().await;
+
+    snapshots::rollback(&window, id, online).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn get_dependency_conflicts(window: Window) -> Result<ConflictReport, String> {
+    let rlbot_source = RequirementSource {
+        name: "RLBot".to_owned(),
+        config_path: None,
+    };
+    let core_packages = packages::load_core_packages(&load_gui_config(&window).await);
+    let mut sources = vec![(rlbot_source, core_packages.iter().filter_map(|package| Requirement::parse(package)).collect())];
+
+    for bot in scan_for_bots(window.clone()).await? {
+        // bots with their own virtual environment never share the global interpreter
+        if bot.use_virtual_environment() {
+            continue;
+        }
+
+        let Some(file) = bot.get_requirements_file() else {
+            continue;
+        };
+
+        match parse_requirements_file(file) {
+            Ok(requirements) => sources.push((
+                RequirementSource {
+                    name: bot.name.clone(),
+                    config_path: Some(bot.path.clone()),
+                },
+                requirements,
+            )),
+            Err(e) => ccprintln!(&window, "{e}"),
+        }
+    }
+
+    let installed = InstalledPackages::query(&PYTHON_PATH.read().await).ok();
+
+    Ok(conflicts::find_conflicts(&sources, installed.as_ref().map(InstalledPackages::markers)))
+}
+
 #[tauri::command]
 pub fn get_console_texts() -> Result<Vec<String>, String> {
     Ok(CONSOLE_TEXT.lock().map_err(|_| "CONSOLE_TEXT lock was poisoned")?.clone())
 }
 
+/// Returns the console history with the most recent command first, which is the order the GUI walks through it in
 #[tauri::command]
 pub fn get_console_input_commands() -> Result<Vec<String>, String> {
-    Ok(CONSOLE_INPUT_COMMANDS.lock().map_err(|_| "CONSOLE_INPUT_COMMANDS lock was poisoned")?.clone())
+    Ok(CONSOLE_INPUT_COMMANDS
+        .lock()
+        .map_err(|_| "CONSOLE_INPUT_COMMANDS lock was poisoned")?
+        .iter()
+        .rev()
+        .cloned()
+        .collect())
 }
 
 #[tauri::command]
-pub async fn run_command(window: Window, input: String) -> Result<(), String> {
+pub async fn complete_console_input(window: Window, input: String) -> Vec<String> {
+    let python_path = PYTHON_PATH.read().await.to_owned();
+    // the bot index is kept up to date by the folder watcher, so the bot folders don't have to be searched on every key press
+    let bot_configs = bot_index::indexed_config_paths(&window).await;
+
+    tauri::async_runtime::spawn_blocking(move || completion::complete(&input, &python_path, &bot_configs))
+        .await
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn run_command(window: Window, input: String) -> Result<Option<u32>, String> {
     #[cfg(windows)]
     const RLPY: &str = "%rlpy%";
     #[cfg(windows)]
@@ -242,7 +371,21 @@ pub async fn run_command(window: Window, input: String) -> Result<(), String> {
     #[cfg(not(windows))]
     const RLPY_ESC: &str = "\\$rlpy";
 
-    CONSOLE_INPUT_COMMANDS.lock().map_err(|err| err.to_string())?.push(input.clone());
+    if let Err(e) = history::push_history(&input) {
+        ccprintln!(&window, "Error saving console history: {e}");
+    }
+
+    if builtins::is_builtin(&input) {
+        builtins::run_builtin(&window, &input).await.map_err(|err| {
+            let e = err.to_string();
+            if !err.is_reported() {
+                ccprintln(&window, &e);
+            }
+            e
+        })?;
+
+        return Ok(None);
+    }
 
     let python_path_lock = PYTHON_PATH.read().await;
     let (program, original_program) = match input.split_whitespace().next().ok_or_else(|| "No command given".to_string())? {
@@ -252,18 +395,58 @@ pub async fn run_command(window: Window, input: String) -> Result<(), String> {
     };
 
     let args = input.strip_prefix(original_program).and_then(shlex::split).unwrap_or_default();
-    spawn_capture_process(program, args).map_err(|err| {
+    jobs::spawn_job(&window, input.clone(), program, args).map(Some).map_err(|err| {
+        let e = err.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub fn send_console_job_input(window: Window, id: u32, input: String) -> Result<(), String> {
+    jobs::send_line(id, &input).map_err(|err| {
         let e = err.to_string();
         ccprintln(&window, &e);
         e
-    })?;
+    })
+}
 
-    Ok(())
+#[tauri::command]
+pub fn kill_console_job(window: Window, id: u32) -> Result<Option<i32>, String> {
+    jobs::kill_job(&window, id).map_err(|err| {
+        let e = err.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub fn get_console_jobs() -> Result<Vec<ConsoleJobInfo>, String> {
+    jobs::list_jobs().map_err(|err| err.to_string())
 }
 
 async fn get_missing_packages_generic<T: Runnable + Send + Sync>(window: &Window, runnables: Vec<T>) -> Vec<MissingPackagesUpdate> {
     if check_has_rlbot().await {
         let python_path = PYTHON_PATH.read().await.to_owned();
+        let pins = load_bot_interpreters(&load_gui_config(window).await);
+
+        // every interpreter is only asked about it's packages once, no matter how many runnables use it
+        let pythons = runnables
+            .iter()
+            .filter(|runnable| runnable.is_rlbot_controlled() && runnable.may_require_python_packages() && runnable.missing_python_packages().is_none())
+            .map(|runnable| interpreter_for(&pins, runnable.get_config_path(), &python_path))
+            .collect::<HashSet<_>>();
+        let installed = pythons
+            .into_par_iter()
+            .filter_map(|python| match InstalledPackages::query(python) {
+                Ok(installed) => Some((python, installed)),
+                Err(e) => {
+                    ccprintln!(window, "Failed to calculate missing packages: {e}");
+                    None
+                }
+            })
+            .collect::<HashMap<_, _>>();
+
         runnables
             .par_iter()
             .enumerate()
@@ -277,7 +460,8 @@ async fn get_missing_packages_generic<T: Runnable + Send + Sync>(window: &Window
                             warn = None;
                         }
                     } else {
-                        let bot_missing_packages = runnable.get_missing_packages(window, &python_path);
+                        let python = interpreter_for(&pins, runnable.get_config_path(), &python_path);
+                        let bot_missing_packages = installed.get(python).map(|installed| runnable.get_missing_packages(window, installed)).unwrap_or_default();
 
                         if bot_missing_packages.is_empty() {
                             warn = None;
@@ -351,6 +535,12 @@ pub fn get_missing_script_logos(scripts: Vec<ScriptConfigBundle>) -> Vec<LogoUpd
     get_missing_logos_generic(&scripts)
 }
 
+/// Get a URL to a thumbnail of a logo, for when a bigger logo than the one in the bot's card is needed
+#[tauri::command]
+pub fn get_logo_thumbnail(path: String, size: LogoSize) -> Option<String> {
+    logo_cache::thumbnail_url(&path, size)
+}
+
 #[tauri::command]
 pub fn is_windows() -> bool {
     cfg!(windows)
@@ -358,8 +548,8 @@ pub fn is_windows() -> bool {
 
 #[derive(Debug, Error)]
 pub enum BootstrapCustomPythonError {
-    #[error("This function is only supported on Windows")]
-    NotWindows,
+    #[error(transparent)]
+    Standalone(#[from] StandalonePythonError),
     #[error("Couldn't download the custom python zip: {0}")]
     Download(#[from] reqwest::Error),
     #[error(transparent)]
@@ -373,14 +563,16 @@ pub enum BootstrapCustomPythonError {
 /// Downloads `RLBot`'s isloated Python 3.7.9 environment and unzips it.
 /// Updates the user with continuous progress updates.
 ///
-/// WORKS FOR WINDOWS ONLY
+/// On Linux & MacOS, a standalone Python is installed from the configured source instead
 ///
 /// # Arguments
 ///
 /// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
-pub async fn bootstrap_custom_python(window: &Window) -> Result<(), BootstrapCustomPythonError> {
+/// * `replace_env` - If the user confirmed that an existing standalone Python environment can be deleted
+pub async fn bootstrap_custom_python(window: &Window, replace_env: bool) -> Result<(), BootstrapCustomPythonError> {
     if cfg!(not(windows)) {
-        return Err(BootstrapCustomPythonError::NotWindows);
+        let source = standalone::load_standalone_python_source(&load_gui_config(window).await);
+        return Ok(standalone::bootstrap_standalone_python(window, source, replace_env).await?);
     }
 
     let content_folder = get_content_folder();
@@ -426,14 +618,20 @@ pub async fn bootstrap_custom_python(window: &Window) -> Result<(), BootstrapCus
 }
 
 #[tauri::command]
-pub async fn install_python(window: Window) -> Result<(), String> {
-    bootstrap_custom_python(&window).await.map_err(|e| {
+pub async fn install_python(window: Window, replace_env: Option<bool>) -> Result<(), String> {
+    bootstrap_custom_python(&window, replace_env.unwrap_or_default()).await.map_err(|e| {
         let e = e.to_string();
         ccprintln(&window, &e);
         e
     })
 }
 
+/// If installing a standalone Python would replace an existing environment, which the user has to confirm first
+#[tauri::command]
+pub fn standalone_python_env_exists() -> bool {
+    cfg!(not(windows)) && standalone::get_env_folder().exists()
+}
+
 #[derive(Debug, Error)]
 pub enum VenvCreationError {
     #[error("Failed to create virtual environment at {0}")]
@@ -445,7 +643,7 @@ pub enum VenvCreationError {
 #[tauri::command]
 pub async fn create_python_venv(window: Window, path: String) -> Result<(), String> {
     async fn inner(path: String) -> Result<(), VenvCreationError> {
-        let python_folder = get_content_folder().join("env");
+        let python_folder = standalone::get_env_folder();
         let python_folder_str = python_folder.to_string_lossy().to_string();
         if !get_command_status(path, ["-m", "venv", &python_folder_str]) {
             return Err(VenvCreationError::Creation(python_folder_str));
@@ -468,6 +666,70 @@ pub async fn create_python_venv(window: Window, path: String) -> Result<(), Stri
     })
 }
 
+#[tauri::command]
+pub async fn create_bot_venv(window: Window, config_path: String) -> Result<BotVenv, String> {
+    let python = PYTHON_PATH.read().await.to_owned();
+
+    venvs::create_bot_venv(&window, &config_path, &python).await.map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn prepare_bot_venv(window: Window, config_path: String) -> Result<Option<BotVenv>, String> {
+    // a pinned interpreter takes priority, so the bot's virtual environment wouldn't be used
+    if load_bot_interpreters(&load_gui_config(&window).await).contains_key(&config_path) {
+        return Ok(None);
+    }
+
+    let python = PYTHON_PATH.read().await.to_owned();
+
+    venvs::ensure_bot_venv(&window, &config_path, &python).await.map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn rebuild_bot_venv(window: Window, config_path: String) -> Result<BotVenv, String> {
+    // rebuild from the same interpreter that the virtual environment was originally created from, if it still runs
+    let base_python = match venvs::load_registry()
+        .await
+        .ok()
+        .and_then(|registry| registry.get(&config_path).map(|venv| venv.base_python.clone()))
+    {
+        Some(base_python) if get_command_status(&base_python, ["--version"]) => base_python,
+        _ => PYTHON_PATH.read().await.to_owned(),
+    };
+
+    venvs::create_bot_venv(&window, &config_path, &base_python).await.map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn delete_bot_venv(window: Window, config_path: String) -> Result<(), String> {
+    venvs::delete_bot_venv(&config_path).await.map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn get_bot_venvs(window: Window) -> Result<BotVenvRegistry, String> {
+    venvs::refresh_registry().await.map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
 #[tauri::command]
 pub async fn download_bot_pack(window: Window) -> Result<String, String> {
     let botpack_location = get_content_folder().join(BOTPACK_FOLDER).to_string_lossy().to_string();
@@ -659,6 +921,58 @@ async fn pre_start_match(window: &Window) -> Result<(), String> {
     Ok(())
 }
 
+/// Under lockstep the game waits for every bot before moving on, so bots that prefer different maximum tick rates can't all get the rate they want
+fn lockstep_tick_rate_warning(bot_list: &[TeamBotBundle]) -> Option<String> {
+    let preferences = bot_list.iter().filter_map(|bot| Some((&bot.name, bot.maximum_tick_rate_preference?))).collect::<Vec<_>>();
+
+    if preferences.iter().map(|(_, rate)| rate).collect::<BTreeSet<_>>().len() < 2 {
+        return None;
+    }
+
+    let bots = preferences.iter().map(|(name, rate)| format!("{name} ({rate})")).collect::<Vec<_>>().join(", ");
+
+    Some(format!(
+        "Warning: lockstep is enabled, but these bots prefer different maximum tick rates: {bots}. Some of them won't run at the rate they expect."
+    ))
+}
+
+/// Picks the Python interpreter for the match handler, which runs every Python bot in the match
+///
+/// Returns an error listing the bots if they need different interpreters, because a bot must never run in an interpreter it wasn't pinned to
+///
+/// # Arguments
+///
+/// * `python_bots` - The name of each Python bot in the match and the interpreter that it needs
+/// * `global_python` - The interpreter that bots without a pinned interpreter or virtual environment use
+fn match_interpreter<'a>(python_bots: &[(&String, &'a str)], global_python: &'a str) -> Result<&'a str, String> {
+    let interpreters = python_bots.iter().map(|(_, python)| *python).collect::<BTreeSet<_>>();
+
+    if interpreters.len() > 1 {
+        let bots = python_bots.iter().map(|(name, python)| format!("{name} ({python})")).collect::<Vec<_>>().join(", ");
+        return Err(format!(
+            "Can't start the match: every Python bot in a match runs in the same interpreter, but these bots need different ones: {bots}. Pin them to the same interpreter or play them in separate matches."
+        ));
+    }
+
+    Ok(interpreters.into_iter().next().unwrap_or(global_python))
+}
+
+/// Shuts down the match handler if it was started with a different Python interpreter, so the next command starts it with `python_path`
+fn stop_match_handler_if_not_using(window: &Window, python_path: &str) -> Result<(), String> {
+    let mut handler_lock = MATCH_HANDLER_STDIN.lock().map_err(|err| err.to_string())?;
+    let (used_py_path, match_handler_stdin) = &mut *handler_lock;
+
+    if used_py_path != python_path {
+        if let Some((_, mut stdin)) = match_handler_stdin.take() {
+            ccprintln!(window, "Restarting the match handler with {python_path}");
+            // if this fails, the old match handler already exited
+            stdin.write_all("shut_down | \n".as_bytes()).ok();
+        }
+    }
+
+    Ok(())
+}
+
 /// Starts a match via the match handler with the given settings
 ///
 /// # Arguments
@@ -666,9 +980,65 @@ async fn pre_start_match(window: &Window) -> Result<(), String> {
 /// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
 /// * `bot_list` - A list of bots and their settings to use in the match
 /// * `match_settings` - The various match settings to use in the match, including scripts (only the path), mutators, game map, etc.
-async fn start_match_helper(window: &Window, bot_list: Vec<TeamBotBundle>, match_settings: MiniMatchConfig, use_pipe: bool) -> Result<(), String> {
+async fn start_match_helper(window: &Window, mut bot_list: Vec<TeamBotBundle>, match_settings: MiniMatchConfig, use_pipe: bool) -> Result<(), String> {
     pre_start_match(window).await?;
 
+    let global_python = PYTHON_PATH.read().await.clone();
+    let pins = load_bot_interpreters(&load_gui_config(window).await);
+
+    // the frontend and story mode only send the path, so the rest comes from the bots' config files
+    let bundles = join_all(bot_list.iter().map(|bot| async move {
+        match &bot.path {
+            Some(path) if bot.runnable_type == "rlbot" => BotConfigBundle::minimal_from_path(path).await.ok(),
+            _ => None,
+        }
+    }))
+    .await;
+
+    // bots that use a virtual environment get one before the match starts, unless the user pinned them to an interpreter
+    for (bot, bundle) in bot_list.iter().zip(&bundles) {
+        let (Some(path), Some(bundle)) = (&bot.path, bundle) else {
+            continue;
+        };
+
+        if bundle.use_virtual_environment() && !pins.contains_key(path) {
+            venvs::ensure_bot_venv(window, path, &global_python)
+                .await
+                .map_err(|e| format!("Couldn't prepare the virtual environment for {}: {e}", bundle.name))?;
+        }
+    }
+
+    let venv_pythons = venvs::use_bot_venvs(bot_list.iter().filter_map(|bot| bot.path.as_ref())).await.unwrap_or_else(|e| {
+        ccprintln!(window, "Error loading bot virtual environments: {e}");
+        HashMap::new()
+    });
+
+    for (bot, bundle) in bot_list.iter_mut().zip(&bundles) {
+        if let Some(bundle) = bundle {
+            bot.supports_early_start = bundle.supports_early_start;
+            bot.maximum_tick_rate_preference = bundle.maximum_tick_rate_preference;
+        }
+    }
+
+    // an interpreter that the user pinned takes priority over the bot's own virtual environment
+    let python_bots = bot_list
+        .iter()
+        .zip(&bundles)
+        .filter_map(|(bot, bundle)| {
+            bundle.as_ref().filter(|bundle| !bundle.python_path.is_empty())?;
+            let path = bot.path.as_ref()?;
+            Some((&bot.name, pins.get(path).or_else(|| venv_pythons.get(path)).unwrap_or(&global_python).as_str()))
+        })
+        .collect::<Vec<_>>();
+
+    let python = match_interpreter(&python_bots, &global_python)?;
+
+    if match_settings.enable_lockstep {
+        if let Some(warning) = lockstep_tick_rate_warning(&bot_list) {
+            ccprintln(window, warning);
+        }
+    }
+
     let launcher_settings = LauncherConfig::load(window).await;
     let match_settings = match_settings.setup_for_start_match(window, &BOT_FOLDER_SETTINGS.read().await.folders)?;
 
@@ -683,7 +1053,8 @@ async fn start_match_helper(window: &Window, bot_list: Vec<TeamBotBundle>, match
 
     println!("Issuing command: {} | ", args.join(" | "));
 
-    issue_match_handler_command(window, &args, CreateHandler::Yes(use_pipe), &*PYTHON_PATH.read().await)?;
+    stop_match_handler_if_not_using(window, python)?;
+    issue_match_handler_command(window, &args, CreateHandler::Yes(use_pipe), python)?;
 
     Ok(())
 }
@@ -777,6 +1148,40 @@ pub async fn spawn_car_for_viewing(window: Window, config: BotLooksConfig, team:
     issue_match_handler_command(&window, &args, CreateHandler::Yes(true), &*PYTHON_PATH.read().await)
 }
 
+/// Runs a bot's loadout generator for both teams, so previews can show the loadout the bot will actually use
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `config_path` - The path to the bot's config file
+/// * `index` - The player index to generate the loadout for
+#[tauri::command]
+pub async fn generate_bot_loadout(window: Window, config_path: String, index: u32) -> Result<BotLooksConfig, String> {
+    let print_err = |e: String| {
+        ccprintln(&window, &e);
+        e
+    };
+
+    let bot = BotConfigBundle::minimal_from_path(&config_path).await.map_err(|e| print_err(e.to_string()))?;
+    let Some(generator_path) = bot.loadout_generator else {
+        return Err(print_err(format!("{} doesn't have a loadout generator", bot.name)));
+    };
+
+    // use the same interpreter that the bot would be ran with
+    let pins = load_bot_interpreters(&load_gui_config(&window).await);
+    let venv_pythons = venvs::use_bot_venvs([&config_path]).await.unwrap_or_default();
+    let python = match pins.get(&config_path).or_else(|| venv_pythons.get(&config_path)) {
+        Some(python) => python.clone(),
+        None => PYTHON_PATH.read().await.clone(),
+    };
+
+    // the generator is a Python process that can take a while, so it's waited on away from the async runtime
+    tauri::async_runtime::spawn_blocking(move || loadout_generator::run(&python, &generator_path, index))
+        .await
+        .map_err(|e| print_err(e.to_string()))?
+        .map_err(|e| print_err(e.to_string()))
+}
+
 #[tauri::command]
 pub async fn get_downloaded_botpack_commit_id() -> Option<u32> {
     get_current_tag_name().await
@@ -794,6 +1199,8 @@ fn make_human_config(team: Team) -> TeamBotBundle {
         skill: 1.0,
         runnable_type: "human".to_owned(),
         path: None,
+        supports_early_start: false,
+        maximum_tick_rate_preference: None,
     }
 }
 
@@ -833,6 +1240,8 @@ fn rlbot_to_player_config(player: &Bot, team: Team, botpack_root: &Path) -> Team
         skill: 1.0,
         runnable_type: "rlbot".to_owned(),
         path: Some(collapse_path(player.path.as_ref(), botpack_root).unwrap_or_default()),
+        supports_early_start: false,
+        maximum_tick_rate_preference: None,
     }
 }
 
@@ -849,6 +1258,8 @@ fn pysonix_to_player_config(player: &Bot, team: Team) -> TeamBotBundle {
         skill: player.skill.unwrap_or(1.0),
         runnable_type: "psyonix".to_owned(),
         path: None,
+        supports_early_start: false,
+        maximum_tick_rate_preference: None,
     }
 }
 
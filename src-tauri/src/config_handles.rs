@@ -1,12 +1,27 @@
 use crate::{
-    bot_management::{cfg_helper::save_cfg, downloader::MapPackUpdater},
+    bot_management::{
+        catalog::{self, CatalogPage, CatalogQuery},
+        cfg_helper::{save_cfg, save_cfg_sync},
+        downloader::MapPackUpdater,
+        duplicates::{self, DuplicateGroup},
+        loadout_randomizer::{self, GeneratedLoadout, LoadoutOptions},
+    },
     custom_maps,
+    python::{
+        interpreters::{self, InterpreterSource, PythonInterpreter},
+        packages,
+        site_packages::InstalledPackages,
+        standalone,
+    },
     rlbot::{
         agents::runnable::Runnable,
         parsing::{
             agent_config_parser::BotLooksConfig,
+            bakkesmod_code,
             bot_config_bundle::{BotConfigBundle, RLBotCfgParseError, ScriptConfigBundle},
-            directory_scanner::{scan_directory_for_bot_configs, scan_directory_for_script_configs},
+            bot_index::{self, BotIndexStats},
+            config_linter::{self, LintReport},
+            item_catalog::{Item, ItemSlot, ITEM_CATALOG},
             match_settings_config_parser::MatchOptions,
         },
     },
@@ -20,8 +35,10 @@ use crate::{
 use configparser::ini::Ini;
 use futures_util::future::join_all;
 use glob::glob;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::Deserialize;
 use std::{
+    cell::RefCell,
     collections::HashMap,
     fs::{create_dir_all, read_to_string},
     path::Path,
@@ -94,7 +111,7 @@ pub fn load_gui_config_sync(window: &Window) -> Ini {
 
         set_gui_config_to_default(&mut conf);
 
-        if let Err(e) = conf.write(&config_path) {
+        if let Err(e) = save_cfg_sync(&conf, &config_path) {
             ccprintln!(window, "Error writing config file: {e}");
         }
     } else if let Err(e) = conf.load(config_path) {
@@ -115,56 +132,81 @@ pub async fn get_folder_settings() -> BotFolders {
     BOT_FOLDER_SETTINGS.read().await.clone()
 }
 
-fn filter_hidden_bundles<I>(bundles: I) -> Vec<I::Item>
-where
-    I: IntoIterator,
-    I::Item: Runnable + Clone,
-{
-    bundles.into_iter().filter(|b| !b.get_config_file_name().starts_with('_')).collect()
+#[tauri::command]
+pub async fn scan_for_bots(window: Window) -> Result<Vec<BotConfigBundle>, String> {
+    Ok(bot_index::scan_bots(&window, &BOT_FOLDER_SETTINGS.read().await.clone()).await)
 }
 
-async fn get_bots_from_directory(window: &Window, path: &str) -> Vec<BotConfigBundle> {
-    filter_hidden_bundles(scan_directory_for_bot_configs(window, path).await)
+#[tauri::command]
+pub async fn scan_for_scripts(window: Window) -> Result<Vec<ScriptConfigBundle>, String> {
+    Ok(bot_index::scan_scripts(&window, &BOT_FOLDER_SETTINGS.read().await.clone()).await)
 }
 
 #[tauri::command]
-pub async fn scan_for_bots(window: Window) -> Result<Vec<BotConfigBundle>, String> {
-    let bfs = BOT_FOLDER_SETTINGS.read().await;
-    let mut bots = Vec::new();
+pub async fn get_bot_index_stats(window: Window) -> BotIndexStats {
+    bot_index::get_stats(&window).await
+}
 
-    for (path, _) in bfs.folders.iter().filter(|(_, props)| props.visible) {
-        bots.extend(get_bots_from_directory(&window, path).await);
-    }
+#[tauri::command]
+pub async fn clear_bot_index(window: Window) -> Result<(), String> {
+    bot_index::clear().await.map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
 
-    for (path, _) in bfs.files.iter().filter(|(_, props)| props.visible) {
-        if let Ok(bundle) = BotConfigBundle::minimal_from_path(Path::new(path)).await {
-            bots.push(bundle);
-        }
-    }
+/// Finds bots that exist more than once in the visible bot folders and files, by name or by content
+#[tauri::command]
+pub async fn get_duplicate_bots(window: Window) -> Vec<DuplicateGroup> {
+    let bots = bot_index::scan_bots(&window, &BOT_FOLDER_SETTINGS.read().await.clone()).await;
+    let preferred_copies = duplicates::load_preferred_copies(&load_gui_config(&window).await);
 
-    Ok(bots)
+    duplicates::find_duplicates(bots, &preferred_copies)
 }
 
-async fn get_scripts_from_directory(window: &Window, path: &str) -> Vec<ScriptConfigBundle> {
-    filter_hidden_bundles(scan_directory_for_script_configs(window, path).await)
+/// Sets which copy of a bot is used when it's looked up by name, or goes back to the newest copy if `config_path` is None
+#[tauri::command]
+pub async fn set_preferred_bot_copy(window: Window, name: String, config_path: Option<String>) {
+    duplicates::set_preferred_copy(&window, name, config_path).await;
 }
 
+/// Searches the bots in the visible bot folders and files, returning one page of results
 #[tauri::command]
-pub async fn scan_for_scripts(window: Window) -> Result<Vec<ScriptConfigBundle>, String> {
-    let bfs = BOT_FOLDER_SETTINGS.read().await.clone();
-    let mut scripts = Vec::with_capacity(bfs.folders.len() + bfs.files.len());
+pub async fn query_bot_catalog(window: Window, query: CatalogQuery) -> CatalogPage {
+    let bot_folders = BOT_FOLDER_SETTINGS.read().await.clone();
+    let bots = bot_index::scan_bots(&window, &bot_folders).await;
+    let ratings = catalog::load_ratings(&window, &bot_folders);
 
-    for (path, _) in bfs.folders.iter().filter(|(_, props)| props.visible) {
-        scripts.extend(get_scripts_from_directory(&window, path).await);
-    }
+    catalog::query(bots, &query, &ratings)
+}
 
-    for (path, _) in bfs.files.iter().filter(|(_, props)| props.visible) {
-        if let Ok(bundle) = ScriptConfigBundle::minimal_from_path(Path::new(path)).await {
-            scripts.push(bundle);
-        }
-    }
+/// Finds every problem in a bot or script config file
+#[tauri::command]
+pub async fn lint_bot_config(window: Window, path: String) -> Result<LintReport, String> {
+    config_linter::lint_config(Path::new(&path)).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
 
-    Ok(scripts)
+/// Lints every bot and script config file in the visible bot folders and files, returning only the files with problems
+#[tauri::command]
+pub async fn lint_all_bot_configs(window: Window) -> Vec<LintReport> {
+    let paths = bot_index::visible_config_paths(&BOT_FOLDER_SETTINGS.read().await.clone());
+
+    paths
+        .par_iter()
+        .filter_map(|path| match config_linter::lint_config(path) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                ccprintln(&window, e.to_string());
+                None
+            }
+        })
+        .filter(|report| report.is_runnable_config && !report.diagnostics.is_empty())
+        .collect()
 }
 
 #[tauri::command]
@@ -223,8 +265,66 @@ pub async fn get_looks(path: String) -> Option<BotLooksConfig> {
 }
 
 #[tauri::command]
-pub async fn save_looks(window: Window, path: String, config: BotLooksConfig) {
+pub async fn save_looks(window: Window, path: String, config: BotLooksConfig) -> Result<(), String> {
+    ITEM_CATALOG.validate(&config).map_err(|e| {
+        let e = format!("Not saving the loadout to {path}: {e}");
+        ccprintln(&window, &e);
+        e
+    })?;
+
     config.save_to_path(&window, &path).await;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn looks_to_bakkesmod_code(window: Window, config: BotLooksConfig) -> Result<String, String> {
+    bakkesmod_code::encode(&config).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn looks_from_bakkesmod_code(window: Window, code: String) -> Result<BotLooksConfig, String> {
+    bakkesmod_code::decode(&code).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })
+}
+
+/// Generate a loadout for both teams, and save it to a bot's looks file if a path is given
+#[tauri::command]
+pub async fn generate_loadout(window: Window, options: LoadoutOptions, path: Option<String>) -> Result<GeneratedLoadout, String> {
+    let loadout = loadout_randomizer::generate(&options).map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })?;
+
+    if let Some(path) = path {
+        save_looks(window, path, loadout.looks.clone()).await?;
+    }
+
+    Ok(loadout)
+}
+
+#[tauri::command]
+pub async fn get_loadout_item(id: u32) -> Option<Item> {
+    ITEM_CATALOG.by_id(id).cloned()
+}
+
+#[tauri::command]
+pub async fn get_loadout_items_by_name(slot: ItemSlot, name: String) -> Vec<Item> {
+    ITEM_CATALOG.by_name(slot, &name).into_iter().cloned().collect()
+}
+
+/// Search for loadout items by name, an empty query gets every item in the slot
+#[tauri::command]
+pub async fn search_loadout_items(query: String, slot: Option<ItemSlot>) -> Vec<Item> {
+    ITEM_CATALOG.search(&query, slot).into_iter().cloned().collect()
 }
 
 #[tauri::command]
@@ -350,6 +450,84 @@ pub async fn set_python_path(window: Window, path: String) -> Result<(), String>
     Ok(())
 }
 
+#[tauri::command]
+pub async fn discover_python_interpreters(window: Window) -> Vec<PythonInterpreter> {
+    let registered = interpreters::load_registered_interpreters(&load_gui_config(&window).await);
+    interpreters::discover_interpreters(&registered)
+}
+
+#[tauri::command]
+pub async fn register_python_interpreter(window: Window, path: String) -> Result<PythonInterpreter, String> {
+    let Some(interpreter) = PythonInterpreter::probe(&path, InterpreterSource::Registered) else {
+        let err = format!("{path} is not a working Python interpreter");
+        ccprintln(&window, &err);
+        return Err(err);
+    };
+
+    let mut registered = interpreters::load_registered_interpreters(&load_gui_config(&window).await);
+    if !registered.contains(&path) {
+        registered.push(path);
+        interpreters::save_registered_interpreters(&window, &registered).await;
+    }
+
+    Ok(interpreter)
+}
+
+#[tauri::command]
+pub async fn unregister_python_interpreter(window: Window, path: String) {
+    let mut registered = interpreters::load_registered_interpreters(&load_gui_config(&window).await);
+    registered.retain(|registered_path| registered_path != &path);
+    interpreters::save_registered_interpreters(&window, &registered).await;
+}
+
+#[tauri::command]
+pub async fn get_bot_interpreters(window: Window) -> HashMap<String, String> {
+    interpreters::load_bot_interpreters(&load_gui_config(&window).await)
+}
+
+#[tauri::command]
+pub async fn set_bot_interpreter(window: Window, config_path: String, python_path: Option<String>) {
+    interpreters::save_bot_interpreter(&window, config_path, python_path).await;
+}
+
+#[tauri::command]
+pub async fn get_standalone_python_source(window: Window) -> Option<String> {
+    standalone::load_standalone_python_source(&load_gui_config(&window).await)
+}
+
+#[tauri::command]
+pub async fn set_standalone_python_source(window: Window, source: Option<String>) {
+    standalone::save_standalone_python_source(&window, source.filter(|source| !source.trim().is_empty())).await;
+}
+
+#[tauri::command]
+pub async fn get_core_packages(window: Window) -> Vec<String> {
+    packages::load_core_packages(&load_gui_config(&window).await)
+}
+
+#[tauri::command]
+pub async fn set_core_package(window: Window, package: String) -> Result<Vec<String>, String> {
+    packages::set_core_package(&window, &package).await.map_err(|e| {
+        let e = e.to_string();
+        ccprintln(&window, &e);
+        e
+    })?;
+
+    Ok(get_core_packages(window).await)
+}
+
+#[tauri::command]
+pub async fn remove_core_package(window: Window, name: String) -> Vec<String> {
+    packages::remove_core_package(&window, &name).await;
+    get_core_packages(window).await
+}
+
+#[tauri::command]
+pub async fn reset_core_packages(window: Window) -> Vec<String> {
+    packages::reset_core_packages(&window).await;
+    get_core_packages(window).await
+}
+
 #[tauri::command]
 pub fn pick_appearance_file(window: Window) {
     FileDialogBuilder::new().add_filter("Appearance Cfg File", &["cfg"]).pick_file(move |path| {
@@ -393,6 +571,12 @@ pub async fn get_recommendations(window: Window) -> Option<AllRecommendations<Bo
     let bfs = BOT_FOLDER_SETTINGS.read().await.clone();
     let python_path = PYTHON_PATH.read().await.to_owned();
     let has_rlbot = check_has_rlbot().await;
+    let gui_config = load_gui_config(&window).await;
+    let pins = interpreters::load_bot_interpreters(&gui_config);
+    let preferred_copies = duplicates::load_preferred_copies(&gui_config);
+
+    // bots are checked against the interpreter that they're pinned to, and each interpreter is only queried once
+    let installed_by_python = RefCell::new(HashMap::<String, Option<InstalledPackages>>::new());
 
     // If we found the json, return the corresponding BotConfigBundles for the bots
     get_recommendations_json(&window, &bfs).map(|j| {
@@ -418,17 +602,26 @@ pub async fn get_recommendations(window: Window) -> Option<AllRecommendations<Bo
         // Get a list of all the bots in (bot name, bot config file path) pairs
         let name_path_pairs = folders.chain(files).collect::<Vec<_>>();
 
-        // Load all of the bot config bundles
+        // Load all of the bot config bundles, picking the preferred copy of bots that exist more than once
         j.change_generic(&|bot_name| {
-            name_path_pairs
+            let copies = name_path_pairs
                 .iter()
                 .filter(|(name, _)| name == bot_name)
-                .find_map(|(_, path)| BotConfigBundle::minimal_from_path_sync(Path::new(path)).ok())
+                .filter_map(|(_, path)| BotConfigBundle::minimal_from_path_sync(Path::new(path)).ok())
+                .collect();
+
+            duplicates::pick_copy(copies, &preferred_copies)
                 .map(|mut bundle| {
                     bundle.logo = bundle.load_logo();
 
-                    if has_rlbot {
-                        let missing_packages = bundle.get_missing_packages(&window, &python_path);
+                    let python = interpreters::interpreter_for(&pins, &bundle.path, &python_path);
+                    let mut installed_by_python = installed_by_python.borrow_mut();
+                    let installed = installed_by_python
+                        .entry(python.to_owned())
+                        .or_insert_with(|| if has_rlbot { InstalledPackages::query(python).ok() } else { None });
+
+                    if let Some(installed) = installed {
+                        let missing_packages = bundle.get_missing_packages(&window, installed);
                         if !missing_packages.is_empty() {
                             bundle.warn = Some("pythonpkg".to_owned());
                         }
@@ -0,0 +1,4 @@
+pub(crate) mod builtins;
+pub(crate) mod completion;
+pub(crate) mod history;
+pub(crate) mod jobs;
@@ -0,0 +1,282 @@
+use crate::{
+    ccprintln,
+    commands::{
+        export_wheelhouse, get_dependency_conflicts, get_python_transaction_diff, get_python_transactions, import_wheelhouse, kill_bots, python_doctor,
+        rollback_python_transaction, set_state, start_match, update_bot_pack, update_map_pack,
+    },
+    config_handles::{get_bot_index_stats, get_team_settings, scan_for_bots, scan_for_scripts, set_python_path},
+    settings::{MatchConfig, MiniMatchConfig, Team, TeamBotBundle},
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tauri::Window;
+use thiserror::Error;
+
+/// The prefix that marks console input as a built-in command instead of a program to run
+pub const BUILTIN_PREFIX: char = ':';
+
+/// The built-in console commands, in the form (usage, description)
+pub const BUILTIN_HELP: [(&str, &str); 14] = [
+    (":help", "Show this list of built-in commands"),
+    (":bots", "List all of the bots in the visible bot folders"),
+    (":scan", "Rescan the visible bot folders for bots and scripts"),
+    (":start", "Start a match with the saved teams and match settings"),
+    (":kill", "Kill the bots in the current match"),
+    (":state <scenario>", "Set the game state to a named scenario or a raw JSON state"),
+    (":update <botpack|mappack>", "Download or update the bot pack or the map pack"),
+    (":python use <path>", "Use the Python interpreter at the given path"),
+    (":python history", "List the recorded package installs"),
+    (":python diff <id>", "Show which packages a recorded install changed"),
+    (":python rollback <id>", "Restore the packages from before a recorded install"),
+    (":python conflicts", "List the packages that bots need incompatible versions of"),
+    (":python doctor", "Check the selected Python for common problems"),
+    (":wheelhouse <export|import> <folder>", "Copy the saved wheels for offline installs to or from a folder"),
+];
+
+/// Game states that can be set with `:state <scenario>`, in the form (name, description)
+pub const STATE_SCENARIOS: [(&str, &str); 6] = [
+    ("kickoff", "Put the ball back on the center spot"),
+    ("airball", "Drop the ball from high above the center spot"),
+    ("pause", "Pause the game"),
+    ("resume", "Unpause the game"),
+    ("slowmo", "Set the game speed to half"),
+    ("normal", "Set the game speed back to normal"),
+];
+
+#[derive(Debug, Error)]
+pub enum BuiltinCommandError {
+    #[error("Unknown built-in command '{0}', type :help for a list of commands")]
+    Unknown(String),
+    #[error("Missing argument: usage is '{0}'")]
+    MissingArgument(&'static str),
+    #[error("Unknown state scenario '{0}', expected one of: {1}")]
+    UnknownScenario(String, String),
+    #[error("Invalid JSON game state: {0}")]
+    InvalidState(#[from] serde_json::Error),
+    #[error("{0}")]
+    Command(String),
+    /// An error from a command that already printed it to the console
+    #[error("{0}")]
+    Reported(String),
+}
+
+impl BuiltinCommandError {
+    /// Check if the error still has to be printed to the console
+    pub const fn is_reported(&self) -> bool {
+        matches!(self, Self::Reported(_))
+    }
+}
+
+impl From<String> for BuiltinCommandError {
+    fn from(error: String) -> Self {
+        Self::Reported(error)
+    }
+}
+
+/// Check if the given console input should be handled by `run_builtin` instead of being ran as a program
+pub fn is_builtin(input: &str) -> bool {
+    input.trim_start().starts_with(BUILTIN_PREFIX)
+}
+
+/// Run a built-in console command, e.x. `:start` or `:python use /usr/bin/python3.9`
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `input` - The full text that the user typed, including the `:` prefix
+pub async fn run_builtin(window: &Window, input: &str) -> Result<(), BuiltinCommandError> {
+    let input = input.trim().trim_start_matches(BUILTIN_PREFIX);
+    let (command, args) = input.split_once(char::is_whitespace).map_or((input, ""), |(command, args)| (command, args.trim()));
+
+    match command {
+        "help" | "" => print_help(window),
+        "bots" => list_bots(window).await?,
+        "scan" => scan(window).await?,
+        "start" => start(window).await?,
+        // the match handler commands don't print all of their errors
+        "kill" => kill_bots(window.clone()).await.map_err(BuiltinCommandError::Command)?,
+        "state" => set_state(window.clone(), scenario_to_state(args)?).await.map_err(BuiltinCommandError::Command)?,
+        "update" => match args {
+            "botpack" => ccprintln(window, update_bot_pack(window.clone()).await?),
+            "mappack" => ccprintln(window, update_map_pack(window.clone()).await?),
+            _ => return Err(BuiltinCommandError::MissingArgument(":update <botpack|mappack>")),
+        },
+        "python" => python(window, args).await?,
+        "wheelhouse" => {
+            let (direction, folder) = args.split_once(char::is_whitespace).map_or((args, ""), |(direction, folder)| (direction, folder.trim()));
+            if folder.is_empty() {
+                return Err(BuiltinCommandError::MissingArgument(":wheelhouse <export|import> <folder>"));
+            }
+
+            let count = match direction {
+                "export" => export_wheelhouse(window.clone(), folder.to_owned())?,
+                "import" => import_wheelhouse(window.clone(), folder.to_owned())?,
+                _ => return Err(BuiltinCommandError::MissingArgument(":wheelhouse <export|import> <folder>")),
+            };
+            ccprintln!(window, "Copied {count} wheels");
+        }
+        command => return Err(BuiltinCommandError::Unknown(command.to_owned())),
+    }
+
+    Ok(())
+}
+
+async fn python(window: &Window, args: &str) -> Result<(), BuiltinCommandError> {
+    let (subcommand, arg) = args.split_once(char::is_whitespace).map_or((args, ""), |(subcommand, arg)| (subcommand, arg.trim()));
+    let parse_id = |usage| arg.parse::<u32>().map_err(|_| BuiltinCommandError::MissingArgument(usage));
+
+    match subcommand {
+        "use" if !arg.is_empty() => {
+            set_python_path(window.clone(), arg.to_owned()).await?;
+            ccprintln!(window, "Now using Python at {arg}");
+        }
+        "use" => return Err(BuiltinCommandError::MissingArgument(":python use <path>")),
+        "history" => {
+            for transaction in get_python_transactions(window.clone())? {
+                ccprintln!(
+                    window,
+                    "  {}: {} in {} - {} changes, exit code {}",
+                    transaction.id,
+                    transaction.operation,
+                    transaction.python,
+                    transaction.changes,
+                    transaction.exit_code
+                );
+            }
+        }
+        "diff" => {
+            for change in get_python_transaction_diff(window.clone(), parse_id(":python diff <id>")?)? {
+                match (change.before, change.after) {
+                    (Some(before), Some(after)) => ccprintln!(window, "  ~ {before} -> {after}"),
+                    (None, Some(after)) => ccprintln!(window, "  + {after}"),
+                    (Some(before), None) => ccprintln!(window, "  - {before}"),
+                    (None, None) => (),
+                }
+            }
+        }
+        "rollback" => {
+            let exit_code = rollback_python_transaction(window.clone(), parse_id(":python rollback <id>")?).await?;
+            ccprintln!(window, "Rollback finished with exit code {exit_code}");
+        }
+        "conflicts" => {
+            let report = get_dependency_conflicts(window.clone()).await?;
+            if report.conflicts.is_empty() {
+                ccprintln(window, "No conflicts found");
+            }
+
+            for conflict in report.conflicts {
+                ccprintln!(window, "{}:", conflict.package);
+                for requirement in conflict.requirements {
+                    ccprintln!(window, "  {} needs {}", requirement.source.name, requirement.requirement);
+                }
+            }
+
+            for config_path in report.isolated_bots {
+                ccprintln!(window, "Can't share the global interpreter: {config_path}");
+            }
+        }
+        "doctor" => {
+            for check in python_doctor(window.clone()).await? {
+                ccprintln!(window, "[{:?}] {}: {}", check.status, check.name, check.message);
+                if let Some(fix) = check.fix {
+                    ccprintln!(window, "  Fix: {fix}");
+                }
+            }
+        }
+        _ => return Err(BuiltinCommandError::MissingArgument(":python <use|history|diff|rollback|conflicts|doctor>")),
+    }
+
+    Ok(())
+}
+
+fn print_help(window: &Window) {
+    ccprintln(window, "Built-in commands:");
+    for (usage, description) in BUILTIN_HELP {
+        ccprintln!(window, "  {usage} - {description}");
+    }
+
+    ccprintln(window, "State scenarios:");
+    for (name, description) in STATE_SCENARIOS {
+        ccprintln!(window, "  {name} - {description}");
+    }
+
+    ccprintln(window, "Anything else is ran as a program; $rlpy is replaced with the selected Python");
+}
+
+async fn list_bots(window: &Window) -> Result<(), BuiltinCommandError> {
+    let bots = scan_for_bots(window.clone()).await?;
+
+    for bot in &bots {
+        ccprintln!(window, "{} - {}", bot.name, bot.path);
+    }
+
+    ccprintln!(window, "Found {} bots", bots.len());
+
+    Ok(())
+}
+
+async fn scan(window: &Window) -> Result<(), BuiltinCommandError> {
+    let bots = scan_for_bots(window.clone()).await?;
+    let scripts = scan_for_scripts(window.clone()).await?;
+
+    let stats = get_bot_index_stats(window.clone()).await;
+
+    ccprintln!(window, "Found {} bots and {} scripts", bots.len(), scripts.len());
+    ccprintln!(
+        window,
+        "Parsed {} changed config files, reused {} from the index",
+        stats.last_bot_scan.parsed,
+        stats.last_bot_scan.reused
+    );
+
+    Ok(())
+}
+
+async fn start(window: &Window) -> Result<(), BuiltinCommandError> {
+    let mut teams = get_team_settings(window.clone()).await;
+    let match_settings = MatchConfig::load(window).await;
+
+    let bot_list = [("blue_team", Team::Blue), ("orange_team", Team::Orange)]
+        .into_iter()
+        .flat_map(|(key, team)| {
+            teams
+                .remove(key)
+                .unwrap_or_default()
+                .iter()
+                .map(|bot| TeamBotBundle::from_bot_bundle(bot, team.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if !bot_list.iter().any(|bot| bot.runnable_type != "human") {
+        ccprintln(window, "Warning: starting a match without any bots, add some to the teams first");
+    }
+
+    ccprintln!(window, "Starting a match with {} players", bot_list.len());
+    start_match(window.clone(), bot_list, MiniMatchConfig::from(&match_settings)).await?;
+
+    Ok(())
+}
+
+/// Convert the argument of `:state` into a game state that the match handler understands
+fn scenario_to_state(scenario: &str) -> Result<HashMap<String, Value>, BuiltinCommandError> {
+    if scenario.starts_with('{') {
+        return Ok(serde_json::from_str(scenario)?);
+    }
+
+    let state = match scenario {
+        "" => return Err(BuiltinCommandError::MissingArgument(":state <scenario>")),
+        "kickoff" => json!({ "ball": { "physics": { "location": { "x": 0, "y": 0, "z": 93 }, "velocity": { "x": 0, "y": 0, "z": 0 } } } }),
+        "airball" => json!({ "ball": { "physics": { "location": { "x": 0, "y": 0, "z": 1500 }, "velocity": { "x": 0, "y": 0, "z": 0 } } } }),
+        "pause" => json!({ "game_info": { "paused": true } }),
+        "resume" => json!({ "game_info": { "paused": false } }),
+        "slowmo" => json!({ "game_info": { "game_speed": 0.5 } }),
+        "normal" => json!({ "game_info": { "game_speed": 1.0 } }),
+        scenario => {
+            let names = STATE_SCENARIOS.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ");
+            return Err(BuiltinCommandError::UnknownScenario(scenario.to_owned(), names));
+        }
+    };
+
+    Ok(serde_json::from_value(state)?)
+}
@@ -0,0 +1,177 @@
+use super::builtins::{BUILTIN_HELP, BUILTIN_PREFIX, STATE_SCENARIOS};
+use crate::{get_command, get_content_folder};
+use std::{
+    collections::BTreeSet,
+    env,
+    fs::read_dir,
+    path::{Path, PathBuf, MAIN_SEPARATOR},
+    process::Stdio,
+    sync::Mutex,
+};
+
+/// The maximum number of suggestions that are returned for a single completion
+const MAX_SUGGESTIONS: usize = 50;
+
+#[cfg(windows)]
+const RLPY: &str = "%rlpy%";
+#[cfg(not(windows))]
+const RLPY: &str = "$rlpy";
+
+/// The Python modules that were found for the last used interpreter, in the form (python path, modules)
+static PYTHON_MODULES_CACHE: Mutex<Option<(String, Vec<String>)>> = Mutex::new(None);
+
+/// Suggests completions for partially typed console input
+///
+/// Only the last word of `input` is completed; every suggestion is the full input line with that word replaced
+///
+/// # Arguments
+///
+/// * `input` - What the user has typed so far
+/// * `python_path` - The path to the Python interpreter that `$rlpy` refers to
+/// * `bot_configs` - The paths to the bot & script config files in the visible bot folders & files
+///
+/// This reads directories and can start Python, so it shouldn't be called on the async runtime
+pub fn complete(input: &str, python_path: &str, bot_configs: &[String]) -> Vec<String> {
+    let (head, partial) = match input.rfind(char::is_whitespace) {
+        Some(index) => input.split_at(index + 1),
+        None => ("", input),
+    };
+    let previous_words = head.split_whitespace().collect::<Vec<_>>();
+
+    let candidates: BTreeSet<String> = match previous_words.as_slice() {
+        [] if partial.starts_with(BUILTIN_PREFIX) => BUILTIN_HELP
+            .iter()
+            .filter_map(|(usage, _)| usage.split_whitespace().next())
+            .map(ToOwned::to_owned)
+            .collect(),
+        [] if is_path_like(partial) => complete_path(partial),
+        [] => {
+            let mut executables = complete_executable(partial);
+            executables.insert(RLPY.to_owned());
+            executables
+        }
+        [":state"] => STATE_SCENARIOS.iter().map(|(name, _)| (*name).to_owned()).collect(),
+        [":update"] => ["botpack", "mappack"].into_iter().map(ToOwned::to_owned).collect(),
+        [":python"] => ["use", "history", "diff", "rollback", "conflicts", "doctor"].into_iter().map(ToOwned::to_owned).collect(),
+        [":wheelhouse"] => ["export", "import"].into_iter().map(ToOwned::to_owned).collect(),
+        [.., "-m"] if previous_words[0] == RLPY => get_python_modules(python_path).into_iter().collect(),
+        _ => {
+            let mut paths = complete_path(partial);
+            paths.extend(bot_configs.iter().filter(|path| path.starts_with(partial)).cloned());
+            paths
+        }
+    };
+
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(partial) && candidate != partial)
+        .take(MAX_SUGGESTIONS)
+        .map(|candidate| {
+            if candidate.contains(char::is_whitespace) {
+                format!("{head}{}", shlex::quote(&candidate))
+            } else {
+                format!("{head}{candidate}")
+            }
+        })
+        .collect()
+}
+
+fn is_path_like(partial: &str) -> bool {
+    partial.contains('/') || partial.contains(MAIN_SEPARATOR) || partial.starts_with('.')
+}
+
+/// Resolves a path the same way a process started from the console would, which is relative to the content folder
+fn resolve(path: &str) -> PathBuf {
+    let path = Path::new(path);
+
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        get_content_folder().join(path)
+    }
+}
+
+/// Suggest files and folders that start with `partial`. Folders get a trailing separator so completion can continue into them.
+fn complete_path(partial: &str) -> BTreeSet<String> {
+    let (dir, prefix) = match partial.rfind(|c| c == '/' || c == MAIN_SEPARATOR) {
+        Some(index) => partial.split_at(index + 1),
+        None => ("", partial),
+    };
+
+    let Ok(entries) = read_dir(resolve(if dir.is_empty() { "." } else { dir })) else {
+        return BTreeSet::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(prefix) {
+                return None;
+            }
+
+            let suffix = if entry.path().is_dir() { MAIN_SEPARATOR.to_string() } else { String::new() };
+            Some(format!("{dir}{name}{suffix}"))
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn executable_name(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_string_lossy().to_ascii_lowercase();
+    let is_executable = ["exe", "bat", "cmd"].contains(&extension.as_str());
+    path.file_stem().filter(|_| is_executable).map(|name| name.to_string_lossy().to_string())
+}
+
+#[cfg(not(windows))]
+fn executable_name(path: &Path) -> Option<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let is_executable = path
+        .metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or_default();
+    path.file_name().filter(|_| is_executable).map(|name| name.to_string_lossy().to_string())
+}
+
+/// Suggest executables on the `PATH` that start with `partial`
+fn complete_executable(partial: &str) -> BTreeSet<String> {
+    let Some(paths) = env::var_os("PATH") else {
+        return BTreeSet::new();
+    };
+
+    env::split_paths(&paths)
+        .filter_map(|dir| read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+        .filter(|path| path.file_name().map_or(false, |name| name.to_string_lossy().starts_with(partial)))
+        .filter_map(|path| executable_name(&path))
+        .collect()
+}
+
+/// Get the names of all of the top-level modules that the given Python interpreter can import
+///
+/// The result is cached per interpreter since asking Python is slow
+fn get_python_modules(python_path: &str) -> Vec<String> {
+    let Ok(mut cache) = PYTHON_MODULES_CACHE.lock() else {
+        return Vec::new();
+    };
+
+    if let Some((cached_path, modules)) = &*cache {
+        if cached_path == python_path {
+            return modules.clone();
+        }
+    }
+
+    let Ok(output) = get_command(python_path, ["-c", "import pkgutil; print('\\n'.join(sorted({m.name for m in pkgutil.iter_modules()})))"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let modules = String::from_utf8_lossy(&output.stdout).lines().map(ToOwned::to_owned).collect::<Vec<_>>();
+    *cache = Some((python_path.to_owned(), modules.clone()));
+
+    modules
+}
@@ -0,0 +1,75 @@
+use crate::{get_content_folder, CONSOLE_INPUT_COMMANDS};
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::PathBuf,
+};
+use thiserror::Error;
+
+/// The maximum number of commands that are remembered between restarts
+const MAX_HISTORY_LENGTH: usize = 500;
+
+#[derive(Debug, Error)]
+pub enum HistoryError {
+    #[error("Mutex {0} was poisoned")]
+    Poisoned(String),
+    #[error("I/O error when managing console history: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Console history is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Get the path to the file where the console history is saved
+fn get_history_path() -> PathBuf {
+    get_content_folder().join("console_history.json")
+}
+
+/// Loads the console history from disk into `CONSOLE_INPUT_COMMANDS`
+///
+/// A missing history file is not an error, the history will just be empty
+pub fn load_history() -> Result<(), HistoryError> {
+    let path = get_history_path();
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let mut history: Vec<String> = serde_json::from_str(&read_to_string(path)?)?;
+    if history.len() > MAX_HISTORY_LENGTH {
+        history.drain(..history.len() - MAX_HISTORY_LENGTH);
+    }
+
+    *CONSOLE_INPUT_COMMANDS.lock().map_err(|_| HistoryError::Poisoned("CONSOLE_INPUT_COMMANDS".to_owned()))? = history;
+
+    Ok(())
+}
+
+/// Adds a command to the end of the console history and saves the history to disk
+///
+/// If the command was already in the history, the old entry is removed so that each command only appears once
+///
+/// # Arguments
+///
+/// * `input` - The command that the user typed
+pub fn push_history(input: &str) -> Result<(), HistoryError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(());
+    }
+
+    let mut history = CONSOLE_INPUT_COMMANDS.lock().map_err(|_| HistoryError::Poisoned("CONSOLE_INPUT_COMMANDS".to_owned()))?;
+    history.retain(|command| command != input);
+    history.push(input.to_owned());
+
+    if history.len() > MAX_HISTORY_LENGTH {
+        let overflow = history.len() - MAX_HISTORY_LENGTH;
+        history.drain(..overflow);
+    }
+
+    let path = get_history_path();
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    write(path, serde_json::to_string(&*history)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use crate::{ccprintln, get_capture_command, CommandError};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    io::Write,
+    process::{Child, ChildStdin, Stdio},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::Duration,
+};
+use tauri::Window;
+use thiserror::Error;
+
+pub const CONSOLE_JOB_FINISHED_SIGNAL: &str = "console-job-finished";
+
+static NEXT_JOB_ID: AtomicU32 = AtomicU32::new(1);
+static CONSOLE_JOBS: Mutex<BTreeMap<u32, ConsoleJob>> = Mutex::new(BTreeMap::new());
+
+/// A process that was started from the GUI console and is still being tracked
+struct ConsoleJob {
+    command: String,
+    child: Child,
+    /// Shared so that input can be written without holding the lock on every job, since a write blocks if the process isn't reading it's stdin
+    stdin: Arc<Mutex<Option<ChildStdin>>>,
+}
+
+/// Information about a tracked console job that the GUI understands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleJobInfo {
+    pub id: u32,
+    pub command: String,
+}
+
+/// Sent to the GUI when a console job exits, for whatever reason
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleJobFinished {
+    pub id: u32,
+    pub command: String,
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Debug, Error)]
+pub enum ConsoleJobError {
+    #[error("Mutex {0} was poisoned")]
+    Poisoned(String),
+    #[error("Failed to start process: {0}")]
+    Spawn(#[from] CommandError),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("No console job with id {0} is running")]
+    NoJob(u32),
+    #[error("The stdin of console job {0} has already been closed")]
+    ClosedStdin(u32),
+}
+
+fn lock_jobs() -> Result<MutexGuard<'static, BTreeMap<u32, ConsoleJob>>, ConsoleJobError> {
+    CONSOLE_JOBS.lock().map_err(|_| ConsoleJobError::Poisoned("CONSOLE_JOBS".to_owned()))
+}
+
+/// Closes the stdin of a job, unless a write to it is still in progress, in which case killing the job ends the write instead
+fn close_stdin(stdin: &Mutex<Option<ChildStdin>>) {
+    if let Ok(mut stdin) = stdin.try_lock() {
+        *stdin = None;
+    }
+}
+
+/// Spawns a process that has it's output captured and sent to the GUI console, and it's stdin kept open so more input can be sent to it later.
+/// The process is tracked until it exits, at which point `CONSOLE_JOB_FINISHED_SIGNAL` is emitted with the exit code.
+///
+/// Returns the ID of the new job
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `command` - The text that the user typed to start the job, used only for display
+/// * `program` - The executable to run
+/// * `args` - The arguments to pass to the executable
+pub fn spawn_job<S: AsRef<OsStr>, A: AsRef<OsStr>, I: IntoIterator<Item = A>>(window: &Window, command: String, program: S, args: I) -> Result<u32, ConsoleJobError> {
+    let mut child = get_capture_command(program, args)?.stdin(Stdio::piped()).spawn()?;
+    let stdin = Arc::new(Mutex::new(child.stdin.take()));
+
+    let id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    lock_jobs()?.insert(id, ConsoleJob { command, child, stdin });
+
+    let window = window.clone();
+    thread::spawn(move || watch_job(&window, id));
+
+    Ok(id)
+}
+
+/// Polls the job with the given ID until it exits, then stops tracking it and tells the GUI
+fn watch_job(window: &Window, id: u32) {
+    let pause_duration = Duration::from_secs_f32(0.25);
+
+    loop {
+        let finished = {
+            let Ok(mut jobs) = lock_jobs() else {
+                return;
+            };
+
+            let Some(job) = jobs.get_mut(&id) else {
+                // the job was removed by someone else, most likely killed
+                return;
+            };
+
+            match job.child.try_wait() {
+                Ok(Some(status)) => jobs.remove(&id).map(|job| (job.command, status.code())),
+                Ok(None) => None,
+                Err(e) => {
+                    ccprintln!(window, "Error checking status of console job {id}: {e}");
+                    jobs.remove(&id).map(|job| (job.command, None))
+                }
+            }
+        };
+
+        if let Some((command, exit_code)) = finished {
+            if let Err(e) = window.emit(CONSOLE_JOB_FINISHED_SIGNAL, ConsoleJobFinished { id, command, exit_code }) {
+                ccprintln!(window, "Error emitting {CONSOLE_JOB_FINISHED_SIGNAL}: {e}");
+            }
+
+            return;
+        }
+
+        thread::sleep(pause_duration);
+    }
+}
+
+/// Writes a line of text to the stdin of a running console job
+///
+/// # Arguments
+///
+/// * `id` - The ID of the job, as returned by `spawn_job`
+/// * `line` - The text to send; a newline is appended
+pub fn send_line(id: u32, line: &str) -> Result<(), ConsoleJobError> {
+    // the lock on every job is released before writing, so a job that isn't reading it's input can't block the others
+    let job_stdin = lock_jobs()?.get(&id).map(|job| job.stdin.clone()).ok_or(ConsoleJobError::NoJob(id))?;
+    let mut job_stdin = job_stdin.lock().map_err(|_| ConsoleJobError::Poisoned(format!("stdin of console job {id}")))?;
+    let stdin = job_stdin.as_mut().ok_or(ConsoleJobError::ClosedStdin(id))?;
+
+    if let Err(e) = stdin.write_all(format!("{line}\n").as_bytes()).and_then(|_| stdin.flush()) {
+        // the process most likely closed it's stdin, so don't try again
+        *job_stdin = None;
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+/// Kills a running console job and stops tracking it
+///
+/// Returns the exit code of the job, if there was one
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `id` - The ID of the job, as returned by `spawn_job`
+pub fn kill_job(window: &Window, id: u32) -> Result<Option<i32>, ConsoleJobError> {
+    let mut job = lock_jobs()?.remove(&id).ok_or(ConsoleJobError::NoJob(id))?;
+
+    // close stdin first so that the child isn't left waiting on it
+    close_stdin(&job.stdin);
+    job.child.kill()?;
+    let exit_code = job.child.wait()?.code();
+
+    if let Err(e) = window.emit(
+        CONSOLE_JOB_FINISHED_SIGNAL,
+        ConsoleJobFinished {
+            id,
+            command: job.command,
+            exit_code,
+        },
+    ) {
+        ccprintln!(window, "Error emitting {CONSOLE_JOB_FINISHED_SIGNAL}: {e}");
+    }
+
+    Ok(exit_code)
+}
+
+/// Returns all of the console jobs that are currently running, oldest first
+pub fn list_jobs() -> Result<Vec<ConsoleJobInfo>, ConsoleJobError> {
+    Ok(lock_jobs()?
+        .iter()
+        .map(|(id, job)| ConsoleJobInfo {
+            id: *id,
+            command: job.command.clone(),
+        })
+        .collect())
+}
+
+/// Kills every running console job, used when the GUI is shutting down
+pub fn kill_all_jobs() -> Result<(), ConsoleJobError> {
+    let jobs = std::mem::take(&mut *lock_jobs()?);
+
+    for (_, mut job) in jobs {
+        close_stdin(&job.stdin);
+
+        // killing fails if the job already exited on it's own, which is fine
+        if job.child.kill().is_ok() {
+            job.child.wait()?;
+        }
+    }
+
+    Ok(())
+}
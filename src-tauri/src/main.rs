@@ -3,7 +3,9 @@
 mod bot_management;
 mod commands;
 mod config_handles;
+mod console;
 mod custom_maps;
+mod python;
 mod rlbot;
 mod settings;
 mod stories;
@@ -15,6 +17,10 @@ use registry::{Hive, Security};
 use std::{os::windows::process::CommandExt, path::Path};
 
 use crate::{
+    bot_management::{
+        folder_watcher::watch_bot_folders,
+        logo_cache::{self, LOGO_PROTOCOL},
+    },
     commands::*,
     config_handles::*,
     settings::{BotFolders, ConsoleTextUpdate, GameTickPacket, StoryConfig, StoryState},
@@ -134,6 +140,11 @@ fn get_python_from_pip(pip: &str) -> Result<String, WindowsPipLocateError> {
 
 #[cfg(target_os = "macos")]
 fn auto_detect_python() -> Option<(String, bool)> {
+    let rlbot_venv_path = get_content_folder().join("env/bin/python");
+    if get_command_status(&rlbot_venv_path, ["--version"]) {
+        return Some((rlbot_venv_path.to_string_lossy().to_string(), true));
+    }
+
     for python in ["python3.7", "python3.8", "python3.9", "python3.6", "python3"] {
         if get_command_status(python, ["--version"]) {
             return Some((python.to_owned(), false));
@@ -552,7 +563,9 @@ fn gui_setup_load_config(window: &Window) {
     tauri_block_on(async {
         let gui_config = load_gui_config(window).await;
         *PYTHON_PATH.write().await = gui_config.get("python_config", "path").unwrap_or_else(|| auto_detect_python().unwrap_or_default().0);
-        *BOT_FOLDER_SETTINGS.write().await = BotFolders::load_from_conf(&load_gui_config(window).await);
+        let bot_folders = BotFolders::load_from_conf(&load_gui_config(window).await);
+        watch_bot_folders(window, &bot_folders);
+        *BOT_FOLDER_SETTINGS.write().await = bot_folders;
     });
 }
 
@@ -591,8 +604,17 @@ fn gui_setup(app: &mut App) -> Result<(), Box<dyn StdError>> {
     });
 
     clear_log_file()?;
+
+    if let Err(e) = logo_cache::prune() {
+        ccprintln!(&window, "Error pruning the logo cache: {e}");
+    }
+
     gui_setup_load_config(&window);
 
+    if let Err(e) = console::history::load_history() {
+        ccprintln!(&window, "Error loading console history: {e}");
+    }
+
     let (mut pipe_reader, pipe_writer) = pipe()?;
     *CAPTURE_PIPE_WRITER.lock()? = Some(pipe_writer);
 
@@ -659,6 +681,7 @@ fn main() {
 
     tauri::Builder::default()
         .setup(|app| gui_setup(app))
+        .register_uri_scheme_protocol(LOGO_PROTOCOL, |_, request| logo_cache::serve(request))
         .plugin(tauri_plugin::init())
         .invoke_handler(tauri::generate_handler![
             get_folder_settings,
@@ -669,7 +692,20 @@ fn main() {
             scan_for_bots,
             get_looks,
             save_looks,
+            looks_to_bakkesmod_code,
+            looks_from_bakkesmod_code,
+            generate_loadout,
+            get_loadout_item,
+            get_loadout_items_by_name,
+            search_loadout_items,
             scan_for_scripts,
+            get_bot_index_stats,
+            clear_bot_index,
+            query_bot_catalog,
+            get_duplicate_bots,
+            set_preferred_bot_copy,
+            lint_bot_config,
+            lint_all_bot_configs,
             get_match_options,
             get_match_settings,
             save_match_settings,
@@ -687,19 +723,40 @@ fn main() {
             install_package,
             install_requirements,
             install_basic_packages,
+            export_wheelhouse,
+            import_wheelhouse,
+            get_python_transactions,
+            get_python_transaction_diff,
+            rollback_python_transaction,
+            get_dependency_conflicts,
             get_console_texts,
             get_console_input_commands,
+            complete_console_input,
             get_detected_python_path,
+            discover_python_interpreters,
+            register_python_interpreter,
+            unregister_python_interpreter,
+            get_bot_interpreters,
+            set_bot_interpreter,
+            get_standalone_python_source,
+            set_standalone_python_source,
+            get_core_packages,
+            set_core_package,
+            remove_core_package,
+            reset_core_packages,
             get_missing_bot_packages,
             get_missing_script_packages,
             get_missing_bot_logos,
             get_missing_script_logos,
+            get_logo_thumbnail,
             is_windows,
             install_python,
+            standalone_python_env_exists,
             download_bot_pack,
             update_bot_pack,
             is_botpack_up_to_date,
             check_rlbot_python,
+            python_doctor,
             update_map_pack,
             start_match,
             get_launcher_settings,
@@ -708,6 +765,7 @@ fn main() {
             fetch_game_tick_packet_json,
             set_state,
             spawn_car_for_viewing,
+            generate_bot_loadout,
             get_downloaded_botpack_commit_id,
             story_load_save,
             story_new_save,
@@ -723,8 +781,16 @@ fn main() {
             recruit,
             is_debug_build,
             run_command,
+            send_console_job_input,
+            kill_console_job,
+            get_console_jobs,
             upload_log,
             create_python_venv,
+            create_bot_venv,
+            prepare_bot_venv,
+            rebuild_bot_venv,
+            delete_bot_venv,
+            get_bot_venvs,
             get_selected_tab,
             set_selected_tab,
             shut_down_match_handler,
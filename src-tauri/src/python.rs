@@ -0,0 +1,10 @@
+pub(crate) mod conflicts;
+pub(crate) mod doctor;
+pub(crate) mod interpreters;
+pub(crate) mod packages;
+pub(crate) mod requirements;
+pub(crate) mod site_packages;
+pub(crate) mod snapshots;
+pub(crate) mod standalone;
+pub(crate) mod venvs;
+pub(crate) mod wheelhouse;
@@ -0,0 +1,179 @@
+use super::requirements::{is_satisfiable, MarkerEnvironment, Requirement};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Where a set of requirements came from
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RequirementSource {
+    /// The name of the bot, or "RLBot" for the packages that the GUI installs itself
+    pub name: String,
+    /// The path to the bot's config file, or None for the packages that the GUI installs itself
+    pub config_path: Option<String>,
+}
+
+/// One source's requirement on a package that's involved in a conflict
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictingRequirement {
+    pub source: RequirementSource,
+    pub requirement: String,
+}
+
+/// A package that no single version could satisfy every requirement on
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyConflict {
+    pub package: String,
+    pub requirements: Vec<ConflictingRequirement>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ConflictReport {
+    pub conflicts: Vec<DependencyConflict>,
+    /// The config paths of the bots that are in at least one conflict, and so can't share the global interpreter
+    pub isolated_bots: Vec<String>,
+}
+
+fn sources_are_satisfiable(requirements: &[&(RequirementSource, Requirement)]) -> bool {
+    is_satisfiable(requirements.iter().flat_map(|(_, requirement)| requirement.specifiers.iter()))
+}
+
+fn to_conflict(package: &str, requirements: &[&(RequirementSource, Requirement)]) -> DependencyConflict {
+    DependencyConflict {
+        package: package.to_owned(),
+        requirements: requirements
+            .iter()
+            .map(|(source, requirement)| ConflictingRequirement {
+                source: source.clone(),
+                requirement: requirement.to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// Finds every package that the given sources need incompatible versions of
+///
+/// Conflicts are narrowed down to the pairs of sources that disagree when possible, so the report names the bots that are actually involved
+///
+/// # Arguments
+///
+/// * `sources` - Every source along with it's requirements
+/// * `markers` - The environment of the global interpreter, used to skip requirements that don't apply to it
+pub fn find_conflicts(sources: &[(RequirementSource, Vec<Requirement>)], markers: Option<&MarkerEnvironment>) -> ConflictReport {
+    let mut by_package: BTreeMap<&str, Vec<(RequirementSource, Requirement)>> = BTreeMap::new();
+
+    for (source, requirements) in sources {
+        for requirement in requirements {
+            if markers.map_or(true, |markers| requirement.applies(markers)) {
+                by_package.entry(&requirement.name).or_default().push((source.clone(), requirement.clone()));
+            }
+        }
+    }
+
+    let mut conflicts = Vec::new();
+
+    for (package, requirements) in &by_package {
+        let all = requirements.iter().collect::<Vec<_>>();
+        if sources_are_satisfiable(&all) {
+            continue;
+        }
+
+        let mut pairs = Vec::new();
+        for (index, a) in all.iter().enumerate() {
+            for b in &all[index + 1..] {
+                if a.0 != b.0 && !sources_are_satisfiable(&[*a, *b]) {
+                    pairs.push(to_conflict(package, &[*a, *b]));
+                }
+            }
+        }
+
+        // if no two sources disagree on their own, it takes all of them together to conflict
+        if pairs.is_empty() {
+            conflicts.push(to_conflict(package, &all));
+        } else {
+            conflicts.extend(pairs);
+        }
+    }
+
+    let isolated_bots = conflicts
+        .iter()
+        .flat_map(|conflict| conflict.requirements.iter().filter_map(|requirement| requirement.source.config_path.clone()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    ConflictReport { conflicts, isolated_bots }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, config_path: Option<&str>, requirements: &[&str]) -> (RequirementSource, Vec<Requirement>) {
+        (
+            RequirementSource {
+                name: name.to_owned(),
+                config_path: config_path.map(str::to_owned),
+            },
+            requirements.iter().map(|requirement| Requirement::parse(requirement).unwrap()).collect(),
+        )
+    }
+
+    fn names(conflict: &DependencyConflict) -> Vec<&str> {
+        conflict.requirements.iter().map(|requirement| requirement.source.name.as_str()).collect()
+    }
+
+    #[test]
+    fn bot_against_core_packages() {
+        let sources = [
+            source("RLBot", None, &["rlbot", "numpy<1.23"]),
+            source("New Bot", Some("new/bot.cfg"), &["numpy>=1.24", "rlbot"]),
+            source("Old Bot", Some("old/bot.cfg"), &["numpy>=1.20"]),
+            source("Web Bot", Some("web/bot.cfg"), &["requests"]),
+        ];
+
+        let report = find_conflicts(&sources, None);
+
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.package, "numpy");
+        assert_eq!(names(conflict), ["RLBot", "New Bot"]);
+        assert_eq!(
+            conflict.requirements.iter().map(|requirement| requirement.requirement.as_str()).collect::<Vec<_>>(),
+            ["numpy<1.23", "numpy>=1.24"]
+        );
+
+        // RLBot itself has no config, and the bots that agree with it can keep sharing the global interpreter
+        assert_eq!(report.isolated_bots, ["new/bot.cfg"]);
+    }
+
+    #[test]
+    fn markers_skip_requirements() {
+        let sources = [
+            source("RLBot", None, &["numpy<1.23"]),
+            source("Old Python Bot", Some("old/bot.cfg"), &["numpy>=1.24; python_version < \"3.8\""]),
+        ];
+
+        let markers = MarkerEnvironment::from([("python_version".to_owned(), "3.11".to_owned())]);
+        let report = find_conflicts(&sources, Some(&markers));
+        assert!(report.conflicts.is_empty());
+        assert!(report.isolated_bots.is_empty());
+
+        // without knowing the interpreter, every requirement counts
+        let report = find_conflicts(&sources, None);
+        assert_eq!(report.isolated_bots, ["old/bot.cfg"]);
+    }
+
+    #[test]
+    fn conflicting_requirements_in_one_source() {
+        let sources = [
+            source("RLBot", None, &["rlbot"]),
+            source("Confused Bot", Some("confused/bot.cfg"), &["numpy>=1.24", "numpy<1.23"]),
+        ];
+
+        // there's no pair of sources to narrow it down to, so every requirement on the package is reported
+        let report = find_conflicts(&sources, None);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].package, "numpy");
+        assert_eq!(names(&report.conflicts[0]), ["Confused Bot", "Confused Bot"]);
+        assert_eq!(report.isolated_bots, ["confused/bot.cfg"]);
+    }
+}
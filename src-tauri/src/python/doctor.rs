@@ -0,0 +1,269 @@
+use super::requirements::{is_satisfiable, Requirement};
+use crate::get_command;
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+
+/// Asks Python about everything that the doctor checks, as JSON
+const DOCTOR_SCRIPT: &str = r#"
+import importlib.util, json, os, sys, sysconfig
+
+def version(name):
+    try:
+        from importlib import metadata
+        return metadata.version(name)
+    except Exception:
+        try:
+            import pkg_resources
+            return pkg_resources.get_distribution(name).version
+        except Exception:
+            return None
+
+def importable(name):
+    try:
+        __import__(name)
+        return True
+    except Exception:
+        return False
+
+site_packages = sysconfig.get_paths()["purelib"]
+print(json.dumps({
+    "version": ".".join(map(str, sys.version_info[:3])),
+    "pip": importlib.util.find_spec("pip") is not None,
+    "ensurepip": importlib.util.find_spec("ensurepip") is not None,
+    "venv": importlib.util.find_spec("venv") is not None,
+    "rlbot": importable("rlbot"),
+    "rlbot_version": version("rlbot"),
+    "rlbot_smh": importable("rlbot_smh"),
+    "rlbot_smh_version": version("rlbot_smh"),
+    "tkinter": importable("tkinter"),
+    "numpy_version": version("numpy"),
+    "numba_version": version("numba"),
+    "site_packages": site_packages,
+    "site_packages_writable": os.access(site_packages, os.W_OK),
+}))
+"#;
+
+/// The first version of each package that has wheels for a Python version, in the form (package, Python minor version, first version)
+///
+/// Older versions have to be built from source, which almost never works on a user's machine
+const FIRST_WHEELS: [(&str, u32, &str); 4] = [("numpy", 11, "1.23.2"), ("numpy", 12, "1.26.0"), ("numba", 11, "0.57.0"), ("numba", 12, "0.59.0")];
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// The result of a single check in the doctor's checklist
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// What the user can do to fix the problem, if there is one
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn new<N: ToString, M: ToString>(name: N, status: CheckStatus, message: M) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.to_string(),
+            fix: None,
+        }
+    }
+
+    fn with_fix<F: ToString>(mut self, fix: F) -> Self {
+        if self.status != CheckStatus::Pass {
+            self.fix = Some(fix.to_string());
+        }
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct DoctorOutput {
+    version: String,
+    pip: bool,
+    ensurepip: bool,
+    venv: bool,
+    rlbot: bool,
+    rlbot_version: Option<String>,
+    rlbot_smh: bool,
+    rlbot_smh_version: Option<String>,
+    tkinter: bool,
+    numpy_version: Option<String>,
+    numba_version: Option<String>,
+    site_packages: String,
+    site_packages_writable: bool,
+}
+
+fn pass_or(passed: bool, otherwise: CheckStatus) -> CheckStatus {
+    if passed {
+        CheckStatus::Pass
+    } else {
+        otherwise
+    }
+}
+
+fn import_check(name: &str, importable: bool, version: Option<&String>) -> DoctorCheck {
+    let message = match (importable, version) {
+        (true, Some(version)) => format!("{name} {version} can be imported"),
+        (true, None) => format!("{name} can be imported"),
+        (false, _) => format!("{name} can't be imported"),
+    };
+
+    DoctorCheck::new(format!("{name} installed"), pass_or(importable, CheckStatus::Fail), message)
+        .with_fix("Install the required packages from the Python configuration menu, or run `$rlpy -m pip install -U rlbot rlbot_smh`")
+}
+
+/// Checks that the installed version and the core package pin of a package both have wheels for the interpreter's Python version
+fn wheel_check(package: &str, minor: u32, installed: Option<&String>, core_packages: &[String]) -> Option<DoctorCheck> {
+    let (_, _, first_wheel) = FIRST_WHEELS.iter().rev().find(|(name, python_minor, _)| *name == package && minor >= *python_minor)?;
+    let needs_wheel = Requirement::parse(&format!("{package}>={first_wheel}"))?;
+    let pin = core_packages
+        .iter()
+        .filter_map(|package| Requirement::parse(package))
+        .find(|requirement| requirement.name == package);
+
+    if let Some(pin) = pin.filter(|pin| !is_satisfiable(pin.specifiers.iter().chain(&needs_wheel.specifiers))) {
+        return Some(
+            DoctorCheck::new(
+                format!("{package} wheels"),
+                CheckStatus::Fail,
+                format!("The core package pin {pin} excludes {package} {first_wheel}, the first version with wheels for Python 3.{minor}"),
+            )
+            .with_fix(format!("Change the {package} pin in the core packages, or use Python 3.7 through 3.10")),
+        );
+    }
+
+    installed.map(|version| {
+        DoctorCheck::new(
+            format!("{package} wheels"),
+            pass_or(needs_wheel.is_satisfied_by(version), CheckStatus::Warn),
+            format!("{package} {version} is installed, and {first_wheel} is the first version with wheels for Python 3.{minor}"),
+        )
+        .with_fix(format!("Upgrade {package} with `$rlpy -m pip install -U {package}`"))
+    })
+}
+
+/// Runs a checklist against a Python interpreter, returning a result for every check
+///
+/// # Arguments
+///
+/// * `python` - The path to, or name of, the Python interpreter to check
+/// * `needs_tkinter` - If any of the scanned bots set `requires_tkinter`
+/// * `core_packages` - The core packages that RLBot installs, with their constraints
+pub fn diagnose(python: &str, needs_tkinter: bool, core_packages: &[String]) -> Vec<DoctorCheck> {
+    let output = get_command(python, ["-c", DOCTOR_SCRIPT]).stdin(Stdio::null()).stderr(Stdio::null()).output();
+    let Some(info) = output
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice::<DoctorOutput>(&output.stdout).ok())
+    else {
+        return vec![DoctorCheck::new("Interpreter runs", CheckStatus::Fail, format!("{python} couldn't be ran"))
+            .with_fix("Pick a different Python in the Python configuration menu, or install the isolated Python")];
+    };
+
+    let mut checks = vec![DoctorCheck::new("Interpreter runs", CheckStatus::Pass, format!("{python} is Python {}", info.version))];
+
+    let minor = info
+        .version
+        .strip_prefix("3.")
+        .and_then(|rest| rest.split('.').next())
+        .and_then(|minor| minor.parse::<u32>().ok());
+    let version_status = match minor {
+        Some(7..=10) => CheckStatus::Pass,
+        Some(6 | 11..) => CheckStatus::Warn,
+        _ => CheckStatus::Fail,
+    };
+    checks.push(
+        DoctorCheck::new(
+            "Supported version",
+            version_status,
+            format!("Python {} - versions 3.7 through 3.10 are supported, 3.7 is the most compatible", info.version),
+        )
+        .with_fix("Install the isolated Python, or pick a Python between 3.7 and 3.10"),
+    );
+
+    checks.push(
+        DoctorCheck::new(
+            "pip available",
+            pass_or(info.pip, CheckStatus::Fail),
+            if info.pip { "pip is installed" } else { "pip is missing" },
+        )
+        .with_fix("Run `$rlpy -m ensurepip` in the console"),
+    );
+
+    #[cfg(windows)]
+    let system_fix = "Reinstall Python with the default options";
+    #[cfg(not(windows))]
+    let system_fix = "Install your distribution's python3-venv package";
+
+    checks.push(
+        DoctorCheck::new(
+            "ensurepip available",
+            pass_or(info.ensurepip, CheckStatus::Warn),
+            if info.ensurepip {
+                "ensurepip is available"
+            } else {
+                "ensurepip is missing, so pip can't be repaired"
+            },
+        )
+        .with_fix(system_fix),
+    );
+    checks.push(
+        DoctorCheck::new(
+            "venv available",
+            pass_or(info.venv && info.ensurepip, CheckStatus::Warn),
+            if info.venv && info.ensurepip {
+                "Virtual environments can be created"
+            } else {
+                "Virtual environments can't be created, so bots can't get their own"
+            },
+        )
+        .with_fix(system_fix),
+    );
+
+    checks.push(import_check("rlbot", info.rlbot, info.rlbot_version.as_ref()));
+    checks.push(import_check("rlbot_smh", info.rlbot_smh, info.rlbot_smh_version.as_ref()));
+
+    if needs_tkinter {
+        #[cfg(windows)]
+        let fix = "Reinstall Python with \"tcl/tk and IDLE\" selected";
+        #[cfg(not(windows))]
+        let fix = "Install your distribution's python3-tk package";
+
+        checks.push(
+            DoctorCheck::new(
+                "tkinter available",
+                pass_or(info.tkinter, CheckStatus::Fail),
+                if info.tkinter {
+                    "tkinter can be imported"
+                } else {
+                    "A scanned bot requires tkinter, but it can't be imported"
+                },
+            )
+            .with_fix(fix),
+        );
+    }
+
+    if let Some(minor) = minor {
+        checks.extend(wheel_check("numpy", minor, info.numpy_version.as_ref(), core_packages));
+        checks.extend(wheel_check("numba", minor, info.numba_version.as_ref(), core_packages));
+    }
+
+    checks.push(
+        DoctorCheck::new(
+            "site-packages writable",
+            pass_or(info.site_packages_writable, CheckStatus::Fail),
+            format!("{} {} be written to", info.site_packages, if info.site_packages_writable { "can" } else { "can't" }),
+        )
+        .with_fix("Use the isolated Python or a virtual environment instead of a system Python"),
+    );
+
+    checks
+}
@@ -0,0 +1,234 @@
+use crate::{bot_management::cfg_helper::save_cfg, ccprintln, get_command, get_config_path, get_content_folder, get_home_folder, load_gui_config};
+use configparser::ini::Ini;
+use glob::glob;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    path::PathBuf,
+    process::Stdio,
+};
+use tauri::Window;
+
+pub(crate) const PYTHON_CONFIG_HEADER: &str = "python_config";
+const REGISTERED_INTERPRETERS_KEY: &str = "interpreters";
+const BOT_INTERPRETERS_KEY: &str = "bot_interpreters";
+
+/// Asks Python for it's real executable path, it's version, and whether or not `rlbot` can be imported - one per line
+const PROBE_SCRIPT: &str =
+    "import sys, importlib.util; print(sys.executable); print('.'.join(map(str, sys.version_info[:3]))); print(importlib.util.find_spec('rlbot') is not None)";
+
+#[cfg(windows)]
+const SYSTEM_PYTHONS: [&str; 2] = ["python", "py"];
+#[cfg(not(windows))]
+const SYSTEM_PYTHONS: [&str; 9] = [
+    "python3.6",
+    "python3.7",
+    "python3.8",
+    "python3.9",
+    "python3.10",
+    "python3.11",
+    "python3.12",
+    "python3",
+    "python",
+];
+
+#[cfg(windows)]
+const VENV_PYTHON: &str = "Scripts/python.exe";
+#[cfg(not(windows))]
+const VENV_PYTHON: &str = "bin/python";
+
+#[cfg(windows)]
+const CONDA_PYTHON: &str = "python.exe";
+#[cfg(not(windows))]
+const CONDA_PYTHON: &str = "bin/python";
+
+/// Where an interpreter was found
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum InterpreterSource {
+    /// One of the environments that the GUI created in the content folder
+    Rlbot,
+    /// A Python on the system `PATH`
+    System,
+    /// A version installed with pyenv, or one of it's shims
+    Pyenv,
+    /// A conda base or named environment
+    Conda,
+    /// An interpreter that the user registered themselves
+    Registered,
+}
+
+/// A Python interpreter that actually ran when probed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PythonInterpreter {
+    /// The path that can be used to run the interpreter
+    pub path: String,
+    /// The path that the interpreter reported as it's `sys.executable`
+    pub executable: String,
+    pub version: String,
+    pub has_rlbot: bool,
+    pub source: InterpreterSource,
+}
+
+impl PythonInterpreter {
+    /// Runs the interpreter at `path` to find out about it, returning None if it doesn't run
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to, or name of, the Python executable
+    /// * `source` - Where the path came from
+    pub fn probe(path: &str, source: InterpreterSource) -> Option<Self> {
+        let output = get_command(path, ["-c", PROBE_SCRIPT]).stdin(Stdio::null()).stderr(Stdio::null()).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines().map(str::trim);
+
+        Some(Self {
+            path: path.to_owned(),
+            executable: lines.next()?.to_owned(),
+            version: lines.next()?.to_owned(),
+            has_rlbot: lines.next()? == "True",
+            source,
+        })
+    }
+}
+
+fn rlbot_candidates() -> Vec<PathBuf> {
+    let content_folder = get_content_folder();
+
+    vec![
+        content_folder.join("Python37/python.exe"),
+        content_folder.join("python-standalone/python/bin/python3"),
+        content_folder.join("env").join(VENV_PYTHON),
+        content_folder.join("venv").join(VENV_PYTHON),
+    ]
+}
+
+fn pyenv_candidates() -> Vec<PathBuf> {
+    let pyenv_root = env::var_os("PYENV_ROOT").map_or_else(|| get_home_folder().0.join(".pyenv"), PathBuf::from);
+
+    let shims = SYSTEM_PYTHONS.iter().map(|python| pyenv_root.join("shims").join(python));
+    let versions = glob(&format!("{}/versions/*/{VENV_PYTHON}", pyenv_root.display()))
+        .map(|paths| paths.flatten().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    shims.chain(versions).collect()
+}
+
+fn conda_candidates() -> Vec<PathBuf> {
+    let home = get_home_folder().0;
+    let mut roots = ["anaconda3", "miniconda3", "miniforge3", "mambaforge"]
+        .into_iter()
+        .map(|name| home.join(name))
+        .collect::<Vec<_>>();
+
+    // CONDA_PREFIX points to the active environment, which might be outside of the usual roots
+    if let Some(prefix) = env::var_os("CONDA_PREFIX") {
+        roots.push(PathBuf::from(prefix));
+    }
+
+    roots
+        .iter()
+        .flat_map(|root| {
+            let envs = glob(&format!("{}/envs/*/{CONDA_PYTHON}", root.display()))
+                .map(|paths| paths.flatten().collect::<Vec<_>>())
+                .unwrap_or_default();
+            [root.join(CONDA_PYTHON)].into_iter().chain(envs)
+        })
+        .collect()
+}
+
+/// Finds every Python interpreter that runs, from the `RLBot` environments, the system `PATH`, pyenv, conda, and the user's registered interpreters
+///
+/// Interpreters that turn out to be the same executable are only listed once, with the first source winning
+///
+/// # Arguments
+///
+/// * `registered` - The interpreters that the user registered themselves
+pub fn discover_interpreters(registered: &[String]) -> Vec<PythonInterpreter> {
+    let to_strings = |paths: Vec<PathBuf>| {
+        paths
+            .into_iter()
+            .filter(|path| path.exists())
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let candidates = [
+        (InterpreterSource::Registered, registered.to_vec()),
+        (InterpreterSource::Rlbot, to_strings(rlbot_candidates())),
+        (InterpreterSource::System, SYSTEM_PYTHONS.iter().map(ToString::to_string).collect()),
+        (InterpreterSource::Pyenv, to_strings(pyenv_candidates())),
+        (InterpreterSource::Conda, to_strings(conda_candidates())),
+    ]
+    .into_iter()
+    .flat_map(|(source, paths)| paths.into_iter().map(move |path| (source, path)))
+    .collect::<Vec<_>>();
+
+    let probed: Vec<PythonInterpreter> = candidates.into_par_iter().filter_map(|(source, path)| PythonInterpreter::probe(&path, source)).collect();
+
+    let mut seen = HashSet::new();
+    probed.into_iter().filter(|interpreter| seen.insert(interpreter.executable.clone())).collect()
+}
+
+/// Load the interpreters that the user has registered from the GUI config
+pub fn load_registered_interpreters(conf: &Ini) -> Vec<String> {
+    conf.get(PYTHON_CONFIG_HEADER, REGISTERED_INTERPRETERS_KEY)
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default()
+}
+
+/// Load the interpreters that have been pinned to bots, as a map of bot config path to Python path
+pub fn load_bot_interpreters(conf: &Ini) -> HashMap<String, String> {
+    conf.get(PYTHON_CONFIG_HEADER, BOT_INTERPRETERS_KEY)
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default()
+}
+
+/// Save the registered interpreters to the GUI config
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `registered` - The full list of registered interpreters
+pub async fn save_registered_interpreters(window: &Window, registered: &[String]) {
+    let mut conf = load_gui_config(window).await;
+    conf.set(PYTHON_CONFIG_HEADER, REGISTERED_INTERPRETERS_KEY, serde_json::to_string(registered).ok());
+
+    if let Err(e) = save_cfg(&conf, get_config_path()).await {
+        ccprintln!(window, "Error saving registered Python interpreters: {e}");
+    }
+}
+
+/// Pin an interpreter to a bot, or remove the pin so the bot uses the global interpreter again
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `config_path` - The path to the bot's config file
+/// * `python_path` - The interpreter to pin, or None to remove the pin
+pub async fn save_bot_interpreter(window: &Window, config_path: String, python_path: Option<String>) {
+    let mut conf = load_gui_config(window).await;
+    let mut pins = load_bot_interpreters(&conf);
+
+    match python_path {
+        Some(python_path) => pins.insert(config_path, python_path),
+        None => pins.remove(&config_path),
+    };
+
+    conf.set(PYTHON_CONFIG_HEADER, BOT_INTERPRETERS_KEY, serde_json::to_string(&pins).ok());
+
+    if let Err(e) = save_cfg(&conf, get_config_path()).await {
+        ccprintln!(window, "Error saving pinned Python interpreters: {e}");
+    }
+}
+
+/// Get the interpreter that should run the bot with the given config, falling back to `default` if none was pinned
+pub fn interpreter_for<'a>(pins: &'a HashMap<String, String>, config_path: &str, default: &'a str) -> &'a str {
+    pins.get(config_path).map_or(default, String::as_str)
+}
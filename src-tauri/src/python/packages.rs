@@ -0,0 +1,128 @@
+use super::{
+    interpreters::PYTHON_CONFIG_HEADER,
+    requirements::{requirement_name, Requirement},
+};
+use crate::{bot_management::cfg_helper::save_cfg, ccprintln, get_config_path, load_gui_config};
+use configparser::ini::Ini;
+use std::collections::BTreeMap;
+use tauri::Window;
+use thiserror::Error;
+
+const PACKAGE_OVERRIDES_KEY: &str = "package_overrides";
+
+/// The packages that RLBot needs, with the constraints that are known to work
+const DEFAULT_PACKAGES: [&str; 9] = [
+    "pip",
+    "setuptools",
+    "wheel",
+    "numpy<1.23",
+    "scipy",
+    "numba<0.56",
+    "selenium",
+    "rlbot==1.*",
+    "rlbot_smh==1.*",
+];
+
+#[derive(Debug, Error)]
+pub enum CorePackageError {
+    #[error("A core package can't be empty")]
+    Empty,
+    #[error("{0} isn't a valid package requirement")]
+    Invalid(String),
+}
+
+/// Load the user's overrides of the core packages, as a map of normalized package name to specifier
+///
+/// An empty specifier means the package was removed from the list
+fn load_overrides(conf: &Ini) -> BTreeMap<String, String> {
+    conf.get(PYTHON_CONFIG_HEADER, PACKAGE_OVERRIDES_KEY)
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default()
+}
+
+async fn save_overrides(window: &Window, overrides: Option<&BTreeMap<String, String>>) {
+    let mut conf = load_gui_config(window).await;
+    conf.set(
+        PYTHON_CONFIG_HEADER,
+        PACKAGE_OVERRIDES_KEY,
+        overrides.and_then(|overrides| serde_json::to_string(overrides).ok()),
+    );
+
+    if let Err(e) = save_cfg(&conf, get_config_path()).await {
+        ccprintln!(window, "Error saving core package overrides: {e}");
+    }
+}
+
+/// Get the core packages that RLBot needs, which are the defaults with the user's overrides applied
+///
+/// Overridden packages keep their place in the list, and new packages are added to the end
+pub fn load_core_packages(conf: &Ini) -> Vec<String> {
+    let mut overrides = load_overrides(conf);
+
+    let mut packages = DEFAULT_PACKAGES
+        .iter()
+        .filter_map(|package| match overrides.remove(&requirement_name(package)) {
+            Some(package) if package.is_empty() => None,
+            Some(package) => Some(package),
+            None => Some((*package).to_owned()),
+        })
+        .collect::<Vec<_>>();
+
+    packages.extend(overrides.into_values().filter(|package| !package.is_empty()));
+
+    packages
+}
+
+/// Apply version constraints from the core packages to the given package name.
+pub fn get_package_name(packages: &[String], package_name: &str) -> String {
+    packages
+        .iter()
+        .find(|package| requirement_name(package) == requirement_name(package_name))
+        .cloned()
+        .unwrap_or_else(|| package_name.to_owned())
+}
+
+/// Add a core package, or change the constraint on an existing one
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `package` - The package with it's constraint, e.x. `numba<0.57`
+pub async fn set_core_package(window: &Window, package: &str) -> Result<(), CorePackageError> {
+    let package = package.trim();
+    if package.is_empty() {
+        return Err(CorePackageError::Empty);
+    }
+
+    let requirement = Requirement::parse_strict(package).ok_or_else(|| CorePackageError::Invalid(package.to_owned()))?;
+
+    let mut overrides = load_overrides(&load_gui_config(window).await);
+    overrides.insert(requirement.name, package.to_owned());
+    save_overrides(window, Some(&overrides)).await;
+
+    Ok(())
+}
+
+/// Remove a package from the core packages, even if it's one of the defaults
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `name` - The name of the package to remove
+pub async fn remove_core_package(window: &Window, name: &str) {
+    let mut overrides = load_overrides(&load_gui_config(window).await);
+    let key = requirement_name(name);
+
+    if DEFAULT_PACKAGES.iter().any(|package| requirement_name(package) == key) {
+        overrides.insert(key, String::new());
+    } else {
+        overrides.remove(&key);
+    }
+
+    save_overrides(window, Some(&overrides)).await;
+}
+
+/// Throw away all of the user's overrides, going back to the built-in core packages
+pub async fn reset_core_packages(window: &Window) {
+    save_overrides(window, None).await;
+}
@@ -0,0 +1,848 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RequirementsError {
+    #[error("Couldn't read requirements file {0}: {1}")]
+    Read(String, std::io::Error),
+}
+
+/// Normalizes a distribution name the way pip does, so `Foo_Bar`, `foo-bar` and `foo.bar` all compare equal
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut last_was_separator = false;
+
+    for c in name.trim().chars() {
+        if c == '-' || c == '_' || c == '.' {
+            if !last_was_separator {
+                normalized.push('-');
+            }
+            last_was_separator = true;
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+            last_was_separator = false;
+        }
+    }
+
+    normalized
+}
+
+/// Gets the normalized name from the start of a requirement or a line of `pip freeze`, like `numba<0.56` or `rlbot @ file:///...`
+pub fn requirement_name(requirement: &str) -> String {
+    let requirement = requirement.trim_start();
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(requirement.len());
+    normalize_name(&requirement[..end])
+}
+
+/// A PEP 440 version, without the local segment
+#[derive(Debug, Clone)]
+pub struct Version {
+    epoch: u64,
+    release: Vec<u64>,
+    /// (0 for alpha, 1 for beta, 2 for release candidate, number)
+    pre: Option<(u8, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+}
+
+/// Splits the leading number off of `s`, returning None if it doesn't start with a digit
+fn take_number(s: &str) -> Option<(u64, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    Some((s[..end].parse().ok()?, &s[end..]))
+}
+
+/// Splits an optional separator and number off of `s`, for segments like `rc1`, `.post2`, or `-dev`
+fn take_segment_number(s: &str) -> (u64, &str) {
+    let s = s.strip_prefix(|c| c == '.' || c == '-' || c == '_').unwrap_or(s);
+    take_number(s).unwrap_or((0, s))
+}
+
+impl Version {
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim().to_ascii_lowercase();
+        let version = version.strip_prefix('v').unwrap_or(&version);
+        let version = version.split('+').next().unwrap_or_default();
+
+        let (epoch, mut rest) = match version.split_once('!') {
+            Some((epoch, rest)) => (epoch.parse().ok()?, rest),
+            None => (0, version),
+        };
+
+        let mut release = Vec::new();
+        loop {
+            let (number, remaining) = take_number(rest)?;
+            release.push(number);
+            rest = remaining;
+
+            match rest.strip_prefix('.') {
+                Some(remaining) if remaining.starts_with(|c: char| c.is_ascii_digit()) => rest = remaining,
+                _ => break,
+            }
+        }
+
+        let mut parsed = Self {
+            epoch,
+            release,
+            pre: None,
+            post: None,
+            dev: None,
+        };
+
+        while !rest.is_empty() {
+            let trimmed = rest.trim_start_matches(|c| c == '.' || c == '-' || c == '_');
+
+            if let Some((kind, remaining)) = [("alpha", 0), ("a", 0), ("beta", 1), ("b", 1), ("preview", 2), ("pre", 2), ("rc", 2), ("c", 2)]
+                .into_iter()
+                .find_map(|(prefix, kind)| trimmed.strip_prefix(prefix).map(|remaining| (kind, remaining)))
+            {
+                let (number, remaining) = take_segment_number(remaining);
+                parsed.pre = Some((kind, number));
+                rest = remaining;
+            } else if let Some(remaining) = ["post", "rev", "r"].into_iter().find_map(|prefix| trimmed.strip_prefix(prefix)) {
+                let (number, remaining) = take_segment_number(remaining);
+                parsed.post = Some(number);
+                rest = remaining;
+            } else if let Some(remaining) = trimmed.strip_prefix("dev") {
+                let (number, remaining) = take_segment_number(remaining);
+                parsed.dev = Some(number);
+                rest = remaining;
+            } else if rest.starts_with('-') {
+                // the implicit post release syntax, like 1.0-1
+                let (number, remaining) = take_number(&rest[1..])?;
+                parsed.post = Some(number);
+                rest = remaining;
+            } else {
+                return None;
+            }
+        }
+
+        Some(parsed)
+    }
+
+    fn trimmed_release(&self) -> &[u64] {
+        let end = self.release.iter().rposition(|&part| part != 0).map_or(0, |index| index + 1);
+        &self.release[..end]
+    }
+
+    fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    fn pre_key(&self) -> (u8, u8, u64) {
+        match (self.pre, self.post, self.dev) {
+            // 1.0.dev0 sorts before 1.0a0
+            (None, None, Some(_)) => (0, 0, 0),
+            (Some((kind, number)), _, _) => (1, kind, number),
+            _ => (2, 0, 0),
+        }
+    }
+
+    fn dev_key(&self) -> (u8, u64) {
+        self.dev.map_or((1, 0), |dev| (0, dev))
+    }
+
+    /// The first release after every version that starts with `prefix`, e.x. 1.3 for `1.2.*`
+    fn next_prefix(epoch: u64, prefix: &[u64]) -> Self {
+        let mut release = prefix.to_vec();
+        if let Some(last) = release.last_mut() {
+            *last += 1;
+        }
+
+        Self {
+            epoch,
+            release,
+            pre: None,
+            post: None,
+            dev: None,
+        }
+    }
+
+    /// Checks if the release segment of this version starts with `prefix`, for `==1.2.*` style specifiers
+    fn matches_prefix(&self, epoch: u64, prefix: &[u64]) -> bool {
+        self.epoch == epoch && prefix.iter().enumerate().all(|(index, part)| self.release.get(index).copied().unwrap_or_default() == *part)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| self.trimmed_release().cmp(other.trimmed_release()))
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            .then_with(|| self.dev_key().cmp(&other.dev_key()))
+    }
+}
+
+// trailing zeros don't count, so 1.0 == 1.0.0
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// One end of a range of versions, in the form (version, inclusive), or None if that end is unbounded
+type Bound = Option<(Version, bool)>;
+
+/// A single version specifier, like `>=1.2` or `~=3.0`
+#[derive(Debug, Clone)]
+pub struct VersionSpecifier {
+    operator: String,
+    version: String,
+}
+
+impl VersionSpecifier {
+    const OPERATORS: [&'static str; 8] = ["===", "==", "!=", "~=", ">=", "<=", ">", "<"];
+
+    fn parse(specifier: &str) -> Option<Self> {
+        let specifier = specifier.trim();
+        let operator = Self::OPERATORS.into_iter().find(|operator| specifier.starts_with(operator))?;
+
+        Some(Self {
+            operator: operator.to_owned(),
+            version: specifier[operator.len()..].trim().to_owned(),
+        })
+    }
+
+    /// Checks if the version can be understood, where `===` allows any version and `==` and `!=` allow a trailing `.*`
+    fn is_valid(&self) -> bool {
+        match self.operator.as_str() {
+            "===" => !self.version.is_empty(),
+            "==" | "!=" => Version::parse(self.version.strip_suffix(".*").unwrap_or(&self.version)).is_some(),
+            _ => Version::parse(&self.version).is_some(),
+        }
+    }
+
+    /// Checks if the installed version `version` satisfies this specifier
+    ///
+    /// Specifiers that can't be understood are assumed to be satisfied, so a strange requirements file doesn't make every bot look broken
+    pub fn contains(&self, version: &str) -> bool {
+        if self.operator == "===" {
+            return version.trim() == self.version;
+        }
+
+        Version::parse(version).map_or(true, |installed| self.contains_version(&installed))
+    }
+
+    fn contains_version(&self, installed: &Version) -> bool {
+        if let Some(prefix) = self.version.strip_suffix(".*") {
+            let Some(prefix) = Version::parse(prefix) else {
+                return true;
+            };
+
+            let matches = installed.matches_prefix(prefix.epoch, &prefix.release);
+            return match self.operator.as_str() {
+                "==" => matches,
+                "!=" => !matches,
+                _ => true,
+            };
+        }
+
+        let Some(wanted) = Version::parse(&self.version) else {
+            return true;
+        };
+
+        match self.operator.as_str() {
+            "==" => *installed == wanted,
+            "!=" => *installed != wanted,
+            ">=" => *installed >= wanted,
+            "<=" => *installed <= wanted,
+            // >1.7 doesn't allow 1.7.post1, and <1.7 doesn't allow 1.7rc1, unless the specifier itself was for one of those
+            ">" => *installed > wanted && (wanted.post.is_some() || installed.epoch != wanted.epoch || installed.trimmed_release() != wanted.trimmed_release()),
+            "<" => *installed < wanted && (wanted.is_prerelease() || !installed.is_prerelease() || installed.trimmed_release() != wanted.trimmed_release()),
+            "~=" => {
+                let prefix = &wanted.release[..wanted.release.len().saturating_sub(1).max(1)];
+                *installed >= wanted && installed.matches_prefix(wanted.epoch, prefix)
+            }
+            _ => true,
+        }
+    }
+
+    /// Get the range of versions that this specifier allows, in the form (lower, upper)
+    ///
+    /// Returns None for specifiers that don't narrow the range, like `!=`, or that can't be understood
+    fn range(&self) -> Option<(Bound, Bound)> {
+        if let Some(prefix) = self.version.strip_suffix(".*") {
+            let prefix = Version::parse(prefix).filter(|_| self.operator == "==")?;
+            let upper = Version::next_prefix(prefix.epoch, &prefix.release);
+            return Some((Some((prefix, true)), Some((upper, false))));
+        }
+
+        let version = Version::parse(&self.version)?;
+
+        Some(match self.operator.as_str() {
+            "==" | "===" => (Some((version.clone(), true)), Some((version, true))),
+            ">=" => (Some((version, true)), None),
+            ">" => (Some((version, false)), None),
+            "<=" => (None, Some((version, true))),
+            "<" => (None, Some((version, false))),
+            "~=" => {
+                let upper = Version::next_prefix(version.epoch, &version.release[..version.release.len().saturating_sub(1).max(1)]);
+                (Some((version, true)), Some((upper, false)))
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// Picks the bound that allows less, where `keep` is the ordering of the bound that should be kept
+fn tighter_bound(a: Bound, b: Bound, keep: Ordering) -> Bound {
+    match (a, b) {
+        (None, bound) | (bound, None) => bound,
+        (Some(a), Some(b)) => Some(match a.0.cmp(&b.0) {
+            Ordering::Equal => (a.0, a.1 && b.1),
+            ordering if ordering == keep => a,
+            _ => b,
+        }),
+    }
+}
+
+/// Checks if there is any version that every one of the specifiers allows, without knowing which versions actually exist
+pub fn is_satisfiable<'a, I: IntoIterator<Item = &'a VersionSpecifier>>(specifiers: I) -> bool {
+    let mut lower = None;
+    let mut upper = None;
+    let mut excluded = Vec::new();
+
+    for specifier in specifiers {
+        match specifier.range() {
+            Some((low, high)) => {
+                lower = tighter_bound(lower, low, Ordering::Greater);
+                upper = tighter_bound(upper, high, Ordering::Less);
+            }
+            None if specifier.operator == "!=" => excluded.push(specifier),
+            None => (),
+        }
+    }
+
+    match (lower, upper) {
+        (Some((low, low_inclusive)), Some((high, high_inclusive))) => match low.cmp(&high) {
+            Ordering::Less => true,
+            // only a single version is allowed, so it can't be one of the excluded ones
+            Ordering::Equal => low_inclusive && high_inclusive && excluded.iter().all(|specifier| specifier.contains_version(&low)),
+            Ordering::Greater => false,
+        },
+        _ => true,
+    }
+}
+
+/// The values of the variables that can be used in environment markers, like `python_version` and `sys_platform`
+pub type MarkerEnvironment = HashMap<String, String>;
+
+const VERSION_MARKERS: [&str; 3] = ["python_version", "python_full_version", "implementation_version"];
+
+#[derive(Debug, PartialEq, Eq)]
+enum MarkerToken {
+    OpenParen,
+    CloseParen,
+    String(String),
+    Variable(String),
+    Operator(&'static str),
+    And,
+    Or,
+}
+
+/// Splits an environment marker into tokens, returning None if it has something that can't be in a marker
+fn tokenize_marker(marker: &str) -> Option<Vec<MarkerToken>> {
+    let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.';
+
+    let mut tokens = Vec::new();
+    let mut rest = marker.trim_start();
+
+    while !rest.is_empty() {
+        let (token, remaining) = if let Some(remaining) = rest.strip_prefix('(') {
+            (MarkerToken::OpenParen, remaining)
+        } else if let Some(remaining) = rest.strip_prefix(')') {
+            (MarkerToken::CloseParen, remaining)
+        } else if let Some(quote) = rest.chars().next().filter(|&c| c == '"' || c == '\'') {
+            let end = rest[1..].find(quote)?;
+            (MarkerToken::String(rest[1..=end].to_owned()), &rest[end + 2..])
+        } else if let Some(operator) = VersionSpecifier::OPERATORS.into_iter().find(|operator| rest.starts_with(operator)) {
+            (MarkerToken::Operator(operator), &rest[operator.len()..])
+        } else {
+            let end = rest.find(|c| !is_identifier(c)).unwrap_or(rest.len());
+            let remaining = &rest[end..];
+
+            match &rest[..end] {
+                "" => return None,
+                "and" => (MarkerToken::And, remaining),
+                "or" => (MarkerToken::Or, remaining),
+                "in" => (MarkerToken::Operator("in"), remaining),
+                "not" => {
+                    let remaining = remaining.trim_start().strip_prefix("in").filter(|remaining| !remaining.starts_with(is_identifier))?;
+                    (MarkerToken::Operator("not in"), remaining)
+                }
+                variable => (MarkerToken::Variable(variable.to_owned()), remaining),
+            }
+        };
+
+        tokens.push(token);
+        rest = remaining.trim_start();
+    }
+
+    Some(tokens)
+}
+
+/// Gets the value of one side of a marker comparison in the form (value, is a version)
+///
+/// Returns None if the token isn't a value, and Some(None) if it's a variable that isn't known
+fn marker_value(token: &MarkerToken, env: &MarkerEnvironment) -> Option<Option<(String, bool)>> {
+    match token {
+        MarkerToken::String(value) => Some(Some((value.clone(), false))),
+        MarkerToken::Variable(name) => {
+            // pip evaluates `extra` as an empty string when no extra was asked for
+            let value = env.get(name).cloned().or_else(|| (name == "extra").then(String::new));
+            Some(value.map(|value| (value, VERSION_MARKERS.contains(&name.as_str()))))
+        }
+        _ => None,
+    }
+}
+
+fn evaluate_comparison((lhs, lhs_is_version): (String, bool), operator: &str, (rhs, rhs_is_version): (String, bool)) -> Option<bool> {
+    Some(match operator {
+        "in" => rhs.contains(&lhs),
+        "not in" => !rhs.contains(&lhs),
+        operator if lhs_is_version || rhs_is_version => VersionSpecifier {
+            operator: operator.to_owned(),
+            version: rhs,
+        }
+        .contains(&lhs),
+        "==" | "===" => lhs == rhs,
+        "!=" => lhs != rhs,
+        _ => return None,
+    })
+}
+
+/// Evaluates the tokens of a marker with PEP 508's grammar, where `and` comes before `or` and parentheses come before both
+struct MarkerParser<'a> {
+    tokens: &'a [MarkerToken],
+    position: usize,
+    env: &'a MarkerEnvironment,
+}
+
+impl<'a> MarkerParser<'a> {
+    fn next(&mut self) -> Option<&'a MarkerToken> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn next_is(&mut self, token: &MarkerToken) -> bool {
+        let is_next = self.tokens.get(self.position) == Some(token);
+        if is_next {
+            self.position += 1;
+        }
+        is_next
+    }
+
+    fn parse_or(&mut self) -> Option<bool> {
+        let mut value = self.parse_and()?;
+        while self.next_is(&MarkerToken::Or) {
+            // both sides are always parsed, so a syntax error on the right side isn't skipped
+            let rhs = self.parse_and()?;
+            value = value || rhs;
+        }
+
+        Some(value)
+    }
+
+    fn parse_and(&mut self) -> Option<bool> {
+        let mut value = self.parse_expression()?;
+        while self.next_is(&MarkerToken::And) {
+            let rhs = self.parse_expression()?;
+            value = value && rhs;
+        }
+
+        Some(value)
+    }
+
+    fn parse_expression(&mut self) -> Option<bool> {
+        if self.next_is(&MarkerToken::OpenParen) {
+            let value = self.parse_or()?;
+            return self.next_is(&MarkerToken::CloseParen).then_some(value);
+        }
+
+        let lhs = marker_value(self.next()?, self.env)?;
+        let MarkerToken::Operator(operator) = self.next()? else {
+            return None;
+        };
+        let rhs = marker_value(self.next()?, self.env)?;
+
+        // comparisons with variables or operators that can't be understood are assumed to be true
+        Some(lhs.zip(rhs).and_then(|(lhs, rhs)| evaluate_comparison(lhs, operator, rhs)).unwrap_or(true))
+    }
+}
+
+/// Evaluates an environment marker like `python_version < "3.8" and (sys_platform == "win32" or os_name == "nt")`
+///
+/// Markers that can't be understood are assumed to be true
+pub fn evaluate_marker(marker: &str, env: &MarkerEnvironment) -> bool {
+    let Some(tokens) = tokenize_marker(marker) else {
+        return true;
+    };
+
+    let mut parser = MarkerParser {
+        tokens: &tokens,
+        position: 0,
+        env,
+    };
+    parser.parse_or().filter(|_| parser.position == tokens.len()).unwrap_or(true)
+}
+
+/// A single requirement from a requirements file, or from a distribution's `Requires-Dist` metadata
+#[derive(Debug, Clone)]
+pub struct Requirement {
+    /// The normalized name of the distribution
+    pub name: String,
+    pub extras: Vec<String>,
+    pub specifiers: Vec<VersionSpecifier>,
+    pub marker: Option<String>,
+    /// The requirement as it was written, without the environment marker
+    spec: String,
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.spec)
+    }
+}
+
+impl Requirement {
+    /// Parses a PEP 508 requirement, returning None for things that aren't a named requirement
+    pub fn parse(line: &str) -> Option<Self> {
+        Self::parse_inner(line, false)
+    }
+
+    /// Parses a requirement like `parse`, but returns None if any of its version specifiers can't be understood instead of ignoring them
+    pub fn parse_strict(line: &str) -> Option<Self> {
+        Self::parse_inner(line, true)
+    }
+
+    fn parse_inner(line: &str, strict: bool) -> Option<Self> {
+        let (spec, marker) = match line.split_once(';') {
+            Some((spec, marker)) => (spec.trim(), Some(marker.trim().to_owned())),
+            None => (line.trim(), None),
+        };
+
+        let name_end = spec.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')).unwrap_or(spec.len());
+        if name_end == 0 {
+            return None;
+        }
+
+        let name = normalize_name(&spec[..name_end]);
+        let mut rest = spec[name_end..].trim_start();
+
+        let mut extras = Vec::new();
+        if let Some(remaining) = rest.strip_prefix('[') {
+            let (inner, remaining) = remaining.split_once(']')?;
+            extras = inner.split(',').map(normalize_name).filter(|extra| !extra.is_empty()).collect();
+            rest = remaining.trim_start();
+        }
+
+        // direct references like `name @ https://...` can't be checked against a version
+        let specifiers = if rest.starts_with('@') {
+            Vec::new()
+        } else {
+            let specifiers = rest
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .split(',')
+                .filter(|specifier| !specifier.trim().is_empty());
+
+            if strict {
+                specifiers
+                    .map(|specifier| VersionSpecifier::parse(specifier).filter(VersionSpecifier::is_valid))
+                    .collect::<Option<_>>()?
+            } else {
+                specifiers.filter_map(VersionSpecifier::parse).collect()
+            }
+        };
+
+        Some(Self {
+            name,
+            extras,
+            specifiers,
+            marker,
+            spec: spec.to_owned(),
+        })
+    }
+
+    /// Checks if the requirement applies in the given environment
+    pub fn applies(&self, env: &MarkerEnvironment) -> bool {
+        self.marker.as_ref().map_or(true, |marker| evaluate_marker(marker, env))
+    }
+
+    /// Checks if `version` satisfies every version specifier
+    pub fn is_satisfied_by(&self, version: &str) -> bool {
+        self.specifiers.iter().all(|specifier| specifier.contains(version))
+    }
+}
+
+/// Removes a trailing comment, which pip only recognizes at the start of a line or after whitespace
+fn strip_comment(line: &str) -> &str {
+    let mut previous = ' ';
+
+    for (index, c) in line.char_indices() {
+        if c == '#' && previous.is_whitespace() {
+            return &line[..index];
+        }
+        previous = c;
+    }
+
+    line
+}
+
+/// Gets the argument of an option like `-r file`, `-rfile`, `--requirement file`, or `--requirement=file`
+fn option_argument<'a>(line: &'a str, short: &str, long: &str) -> Option<&'a str> {
+    if let Some(rest) = line.strip_prefix(long) {
+        return Some(rest.trim_start_matches('=').trim());
+    }
+
+    line.strip_prefix(short).map(str::trim)
+}
+
+fn parse_requirements_file_inner(path: &Path, visited: &mut HashSet<PathBuf>, requirements: &mut Vec<Requirement>) -> Result<(), RequirementsError> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if !visited.insert(canonical) {
+        return Ok(());
+    }
+
+    let contents = read_to_string(path).map_err(|e| RequirementsError::Read(path.to_string_lossy().to_string(), e))?;
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+
+    // a backslash at the end of a line continues the line
+    let joined = contents.replace("\\\r\n", "").replace("\\\n", "");
+
+    for line in joined.lines() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(include) = option_argument(line, "-r", "--requirement") {
+            parse_requirements_file_inner(&parent.join(include), visited, requirements)?;
+            continue;
+        }
+
+        // constraint files, editable installs, index options, and direct URLs or paths don't name anything that can be checked
+        if line.starts_with('-') || line.starts_with('.') || line.starts_with('/') || line.contains("://") {
+            continue;
+        }
+
+        if let Some(requirement) = Requirement::parse(line) {
+            requirements.push(requirement);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a requirements file, following any `-r` includes relative to the including file
+///
+/// # Arguments
+///
+/// * `path` - The path to the requirements file
+pub fn parse_requirements_file<P: AsRef<Path>>(path: P) -> Result<Vec<Requirement>, RequirementsError> {
+    let mut requirements = Vec::new();
+    parse_requirements_file_inner(path.as_ref(), &mut HashSet::new(), &mut requirements)?;
+
+    Ok(requirements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    fn specifier(specifier: &str) -> VersionSpecifier {
+        VersionSpecifier::parse(specifier).unwrap()
+    }
+
+    fn env(extra: Option<&str>) -> MarkerEnvironment {
+        let mut env = [("python_version", "3.7"), ("python_full_version", "3.7.9"), ("os_name", "nt"), ("sys_platform", "win32")]
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect::<MarkerEnvironment>();
+
+        if let Some(extra) = extra {
+            env.insert("extra".to_owned(), extra.to_owned());
+        }
+
+        env
+    }
+
+    #[test]
+    fn version_ordering() {
+        let ordered = [
+            "1.0.dev0",
+            "1.0a1.dev1",
+            "1.0a1",
+            "1.0a2",
+            "1.0b1",
+            "1.0rc1",
+            "1.0",
+            "1.0.post1.dev0",
+            "1.0.post1",
+            "1.1.dev1",
+            "1.1",
+            "1.10",
+            "1!0.1",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} should be before {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn version_equality() {
+        for (a, b) in [
+            ("1.0", "1.0.0"),
+            ("v1.0", "1.0"),
+            ("1.0+local", "1.0"),
+            ("1.0-1", "1.0.post1"),
+            ("1.0alpha1", "1.0a1"),
+            ("1.0c1", "1.0rc1"),
+        ] {
+            assert_eq!(version(a), version(b), "{a} should equal {b}");
+        }
+
+        assert!(Version::parse("not a version").is_none());
+    }
+
+    #[test]
+    fn compatible_release() {
+        let two_parts = specifier("~=2.2");
+        assert!(two_parts.contains("2.2"));
+        assert!(two_parts.contains("2.9.1"));
+        assert!(!two_parts.contains("2.1"));
+        assert!(!two_parts.contains("3.0"));
+
+        let three_parts = specifier("~=1.4.5");
+        assert!(three_parts.contains("1.4.5"));
+        assert!(three_parts.contains("1.4.9"));
+        assert!(!three_parts.contains("1.4.4"));
+        assert!(!three_parts.contains("1.5.0"));
+    }
+
+    #[test]
+    fn prefix_matching() {
+        let equal = specifier("==1.1.*");
+        assert!(equal.contains("1.1"));
+        assert!(equal.contains("1.1.5"));
+        assert!(!equal.contains("1.10"));
+        assert!(!equal.contains("1.2"));
+
+        let not_equal = specifier("!=1.1.*");
+        assert!(not_equal.contains("1.2"));
+        assert!(!not_equal.contains("1.1.3"));
+    }
+
+    #[test]
+    fn exclusive_comparisons() {
+        assert!(!specifier(">1.7").contains("1.7.post1"));
+        assert!(specifier(">1.7").contains("1.7.1"));
+        assert!(!specifier("<1.7").contains("1.7rc1"));
+        assert!(specifier("<1.7").contains("1.6"));
+    }
+
+    #[test]
+    fn satisfiable_specifiers() {
+        assert!(is_satisfiable(&[specifier(">=1.0"), specifier("<2.0")]));
+        assert!(!is_satisfiable(&[specifier(">=2.0"), specifier("<2.0")]));
+        assert!(!is_satisfiable(&[specifier("==1.5"), specifier("!=1.5")]));
+        assert!(!is_satisfiable(&[specifier("~=1.4.5"), specifier(">=1.5")]));
+    }
+
+    #[test]
+    fn markers() {
+        let env = env(None);
+
+        assert!(evaluate_marker(r#"python_version < "3.8""#, &env));
+        assert!(!evaluate_marker(r#"python_version >= "3.8""#, &env));
+        // versions are compared as versions, not strings
+        assert!(!evaluate_marker(r#"python_version > "3.10""#, &env));
+        assert!(evaluate_marker(r#""win" in sys_platform and 'linux' not in sys_platform"#, &env));
+    }
+
+    #[test]
+    fn marker_precedence() {
+        let env = env(None);
+
+        // and comes before or
+        assert!(!evaluate_marker(r#"os_name == "posix" or python_version < "3.8" and sys_platform == "linux""#, &env));
+        assert!(evaluate_marker(r#"sys_platform == "linux" and os_name == "posix" or python_version < "3.8""#, &env));
+        assert!(evaluate_marker(
+            r#"sys_platform == "win32" and (python_version >= "3.8" or python_full_version == "3.7.9")"#,
+            &env
+        ));
+        assert!(!evaluate_marker(r#"(python_version >= "3.8" or os_name == "posix") and sys_platform == "win32""#, &env));
+    }
+
+    #[test]
+    fn marker_extras() {
+        let marker = r#"(python_version < "3.8" or os_name == "posix") and extra == "x""#;
+
+        assert!(!evaluate_marker(marker, &env(None)));
+        assert!(evaluate_marker(marker, &env(Some("x"))));
+        assert!(!evaluate_marker(marker, &env(Some("y"))));
+    }
+
+    #[test]
+    fn unknown_markers() {
+        let env = env(None);
+
+        assert!(evaluate_marker(r#"implementation_name == "pypy""#, &env));
+        assert!(evaluate_marker(r#"python_version <"#, &env));
+        assert!(evaluate_marker(r#"(python_version >= "3.8""#, &env));
+    }
+
+    #[test]
+    fn requirements() {
+        let requirement = Requirement::parse(r#"Foo_Bar[Extra] >=1.0, <2 ; python_version < "3.8""#).unwrap();
+        assert_eq!(requirement.name, "foo-bar");
+        assert_eq!(requirement.extras, ["extra"]);
+        assert!(requirement.applies(&env(None)));
+        assert!(requirement.is_satisfied_by("1.5"));
+        assert!(!requirement.is_satisfied_by("2.0"));
+    }
+
+    #[test]
+    fn strict_requirements() {
+        for valid in [
+            "numba<0.57",
+            "rlbot==1.*",
+            "numpy >=1.20, <1.23",
+            "selenium",
+            "rlbot @ https://example.com/rlbot.whl",
+            "odd===anything",
+        ] {
+            assert!(Requirement::parse_strict(valid).is_some(), "{valid}");
+        }
+
+        for invalid in ["", "<1.0", "numba 0.57", "numba<", "numba<abc", "numba>=1.0, nonsense"] {
+            assert!(Requirement::parse_strict(invalid).is_none(), "{invalid}");
+        }
+
+        // the lenient parser still ignores what it can't understand
+        assert!(Requirement::parse("numba 0.57").is_some());
+    }
+}
@@ -0,0 +1,200 @@
+use super::requirements::{normalize_name, parse_requirements_file, MarkerEnvironment, Requirement, RequirementsError};
+use crate::get_command;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs::{read_dir, read_to_string},
+    path::Path,
+    process::Stdio,
+};
+use thiserror::Error;
+
+/// Asks Python for it's import paths, it's environment marker values, and whether or not `tkinter` can be imported, as JSON
+const QUERY_SCRIPT: &str = r#"
+import json, os, platform, sys
+try:
+    import tkinter
+    has_tkinter = True
+except ImportError:
+    has_tkinter = False
+print(json.dumps({
+    "paths": [path for path in sys.path if path],
+    "has_tkinter": has_tkinter,
+    "markers": {
+        "os_name": os.name,
+        "sys_platform": sys.platform,
+        "platform_machine": platform.machine(),
+        "platform_python_implementation": platform.python_implementation(),
+        "platform_release": platform.release(),
+        "platform_system": platform.system(),
+        "platform_version": platform.version(),
+        "python_version": ".".join(platform.python_version_tuple()[:2]),
+        "python_full_version": platform.python_version(),
+        "implementation_name": sys.implementation.name,
+        "implementation_version": ".".join(map(str, sys.implementation.version[:3])),
+    },
+}))
+"#;
+
+#[derive(Debug, Error)]
+pub enum SitePackagesError {
+    #[error("Couldn't run {0}: {1}")]
+    Run(String, std::io::Error),
+    #[error("{0} exited with {1}")]
+    Failed(String, std::process::ExitStatus),
+    #[error("Couldn't understand the output of {0}: {1}")]
+    Json(String, serde_json::Error),
+}
+
+#[derive(Deserialize)]
+struct QueryOutput {
+    paths: Vec<String>,
+    has_tkinter: bool,
+    markers: MarkerEnvironment,
+}
+
+/// A distribution that's installed into an interpreter
+#[derive(Debug, Clone)]
+struct Distribution {
+    version: String,
+    requires_dist: Vec<String>,
+}
+
+/// Everything that's installed into a single interpreter, read straight from the `*.dist-info` and `*.egg-info` metadata on disk
+#[derive(Debug, Clone)]
+pub struct InstalledPackages {
+    distributions: HashMap<String, Distribution>,
+    markers: MarkerEnvironment,
+    has_tkinter: bool,
+}
+
+/// Reads the name, version, and requirements out of a `METADATA` or `PKG-INFO` file
+fn read_metadata(path: &Path) -> Option<(String, Distribution)> {
+    let contents = read_to_string(path).ok()?;
+    let mut name = None;
+    let mut version = None;
+    let mut requires_dist = Vec::new();
+
+    // the headers end at the first blank line, and the long description comes after
+    for line in contents.lines().take_while(|line| !line.trim().is_empty()) {
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = name.or_else(|| Some(normalize_name(value)));
+        } else if let Some(value) = line.strip_prefix("Version:") {
+            version = version.or_else(|| Some(value.trim().to_owned()));
+        } else if let Some(value) = line.strip_prefix("Requires-Dist:") {
+            requires_dist.push(value.trim().to_owned());
+        }
+    }
+
+    Some((name?, Distribution { version: version?, requires_dist }))
+}
+
+impl InstalledPackages {
+    /// Runs the interpreter once to find out where it's packages are, then reads all of the installed distributions from disk
+    ///
+    /// # Arguments
+    ///
+    /// * `python` - The path to, or name of, the Python interpreter
+    pub fn query(python: &str) -> Result<Self, SitePackagesError> {
+        let output = get_command(python, ["-c", QUERY_SCRIPT])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| SitePackagesError::Run(python.to_owned(), e))?;
+
+        if !output.status.success() {
+            return Err(SitePackagesError::Failed(python.to_owned(), output.status));
+        }
+
+        let query: QueryOutput = serde_json::from_slice(&output.stdout).map_err(|e| SitePackagesError::Json(python.to_owned(), e))?;
+        let mut distributions = HashMap::new();
+
+        for entry in query.paths.iter().filter_map(|path| read_dir(path).ok()).flat_map(|entries| entries.flatten()) {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            let metadata_path = if file_name.ends_with(".dist-info") {
+                path.join("METADATA")
+            } else if file_name.ends_with(".egg-info") {
+                if path.is_dir() {
+                    path.join("PKG-INFO")
+                } else {
+                    path
+                }
+            } else {
+                continue;
+            };
+
+            if let Some((name, distribution)) = read_metadata(&metadata_path) {
+                // earlier entries on sys.path shadow later ones, just like when importing
+                distributions.entry(name).or_insert(distribution);
+            }
+        }
+
+        Ok(Self {
+            distributions,
+            markers: query.markers,
+            has_tkinter: query.has_tkinter,
+        })
+    }
+
+    /// Get the values of the environment markers for this interpreter
+    pub fn markers(&self) -> &MarkerEnvironment {
+        &self.markers
+    }
+
+    /// Checks if a requirement is satisfied, including the requirements of any extras it asks for
+    fn is_satisfied(&self, requirement: &Requirement) -> bool {
+        let Some(distribution) = self.distributions.get(&requirement.name) else {
+            return false;
+        };
+
+        if !requirement.is_satisfied_by(&distribution.version) {
+            return false;
+        }
+
+        requirement.extras.iter().all(|extra| {
+            let mut markers = self.markers.clone();
+            markers.insert("extra".to_owned(), extra.clone());
+
+            distribution
+                .requires_dist
+                .iter()
+                .filter_map(|requires| Requirement::parse(requires))
+                .filter(|requires| requires.marker.as_ref().map_or(false, |marker| marker.contains("extra")) && requires.applies(&markers))
+                .all(|requires| {
+                    self.distributions
+                        .get(&requires.name)
+                        .map_or(false, |dependency| requires.is_satisfied_by(&dependency.version))
+                })
+        })
+    }
+
+    /// Get the requirements that apply to this interpreter's environment but aren't satisfied by what's installed
+    pub fn missing<'a>(&self, requirements: &'a [Requirement]) -> Vec<&'a Requirement> {
+        requirements
+            .iter()
+            .filter(|requirement| requirement.applies(&self.markers) && !self.is_satisfied(requirement))
+            .collect()
+    }
+}
+
+/// Get every package from a runnable's requirements that isn't installed, in the same format as `rlbot_smh.get_missing_packages`
+///
+/// # Arguments
+///
+/// * `requirements_file` - The runnable's requirements file, if it has one
+/// * `requires_tkinter` - If the runnable needs `tkinter`, which can't be installed with pip
+/// * `installed` - The packages that are installed into the runnable's interpreter
+pub fn get_missing_packages(requirements_file: Option<&str>, requires_tkinter: bool, installed: &InstalledPackages) -> Result<Vec<String>, RequirementsError> {
+    let mut missing = match requirements_file {
+        Some(file) => installed.missing(&parse_requirements_file(file)?).into_iter().map(ToString::to_string).collect(),
+        None => Vec::new(),
+    };
+
+    if requires_tkinter && !installed.has_tkinter {
+        missing.push(String::from("tkinter"));
+    }
+
+    Ok(missing)
+}
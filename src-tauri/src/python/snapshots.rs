@@ -0,0 +1,268 @@
+use super::{requirements::requirement_name, wheelhouse};
+use crate::{ccprintln, get_command, get_content_folder, spawn_capture_process_and_get_exit_code};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, read_to_string, remove_file, write},
+    path::PathBuf,
+    process::Stdio,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tauri::Window;
+use thiserror::Error;
+
+/// The maximum number of transactions that are kept in the log
+const MAX_TRANSACTIONS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("I/O error when managing the Python transaction log: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Python transaction log is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("There is no Python transaction with id {0}")]
+    NoTransaction(u32),
+    #[error("Couldn't get the installed packages from {0}")]
+    Freeze(String),
+}
+
+/// A single install, with what was installed before and after it ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PythonTransaction {
+    pub id: u32,
+    /// The interpreter that the packages were installed into
+    pub python: String,
+    /// What was done, e.x. `install numpy` or `install -r requirements.txt`
+    pub operation: String,
+    /// When the operation finished, in seconds since the Unix epoch
+    pub time: u64,
+    pub exit_code: i32,
+    /// The output of `pip freeze --all` before the operation
+    pub before: Vec<String>,
+    /// The output of `pip freeze --all` after the operation
+    pub after: Vec<String>,
+}
+
+/// A transaction without the full snapshots, for listing in the GUI
+#[derive(Debug, Clone, Serialize)]
+pub struct PythonTransactionSummary {
+    pub id: u32,
+    pub python: String,
+    pub operation: String,
+    pub time: u64,
+    pub exit_code: i32,
+    /// How many packages were added, removed, or changed version
+    pub changes: usize,
+}
+
+/// A package that is different between two snapshots
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageChange {
+    pub name: String,
+    /// The freeze line before, or None if the package was added
+    pub before: Option<String>,
+    /// The freeze line after, or None if the package was removed
+    pub after: Option<String>,
+}
+
+fn get_transaction_log_path() -> PathBuf {
+    get_content_folder().join("python_transactions.json")
+}
+
+fn load_transactions() -> Result<Vec<PythonTransaction>, SnapshotError> {
+    let path = get_transaction_log_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(serde_json::from_str(&read_to_string(path)?)?)
+}
+
+fn save_transactions(transactions: &[PythonTransaction]) -> Result<(), SnapshotError> {
+    let path = get_transaction_log_path();
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    write(path, serde_json::to_string(transactions)?)?;
+
+    Ok(())
+}
+
+/// Get the output of `pip freeze --all`, one requirement per line
+fn freeze(python: &str) -> Result<Vec<String>, SnapshotError> {
+    let output = get_command(python, ["-m", "pip", "freeze", "--all"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|_| SnapshotError::Freeze(python.to_owned()))?;
+
+    if !output.status.success() {
+        return Err(SnapshotError::Freeze(python.to_owned()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+fn by_name(snapshot: &[String]) -> BTreeMap<String, &String> {
+    snapshot.iter().map(|line| (requirement_name(line), line)).collect()
+}
+
+/// Get every package that is different between two snapshots, sorted by name
+pub fn diff_snapshots(before: &[String], after: &[String]) -> Vec<PackageChange> {
+    let before = by_name(before);
+    let after = by_name(after);
+
+    let mut names = before.keys().chain(after.keys()).collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let before = before.get(name).map(|line| (*line).clone());
+            let after = after.get(name).map(|line| (*line).clone());
+
+            (before != after).then(|| PackageChange {
+                name: name.clone(),
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+fn push_transaction(python: &str, operation: String, exit_code: i32, before: Vec<String>, after: Vec<String>) -> Result<u32, SnapshotError> {
+    let mut transactions = load_transactions()?;
+    let id = transactions.last().map_or(0, |transaction| transaction.id + 1);
+
+    transactions.push(PythonTransaction {
+        id,
+        python: python.to_owned(),
+        operation,
+        time: SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default(),
+        exit_code,
+        before,
+        after,
+    });
+
+    if transactions.len() > MAX_TRANSACTIONS {
+        let overflow = transactions.len() - MAX_TRANSACTIONS;
+        transactions.drain(..overflow);
+    }
+
+    save_transactions(&transactions)?;
+
+    Ok(id)
+}
+
+/// Runs an install, recording a snapshot of the installed packages before and after it in the transaction log
+///
+/// Failing to take a snapshot doesn't stop the install, it's just not recorded. Returns the exit code of `install`.
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `python` - The interpreter that the packages are being installed into
+/// * `operation` - A short description of the install, for the log
+/// * `install` - Does the install and returns pip's exit code
+pub fn record<F: FnOnce() -> i32>(window: &Window, python: &str, operation: String, install: F) -> i32 {
+    let before = freeze(python);
+    let exit_code = install();
+
+    let result = before.and_then(|before| {
+        let after = freeze(python)?;
+        push_transaction(python, operation, exit_code, before, after)
+    });
+
+    if let Err(e) = result {
+        ccprintln!(window, "Couldn't record the Python environment snapshot: {e}");
+    }
+
+    exit_code
+}
+
+/// Get every transaction in the log, oldest first
+pub fn list_transactions() -> Result<Vec<PythonTransactionSummary>, SnapshotError> {
+    Ok(load_transactions()?
+        .into_iter()
+        .map(|transaction| PythonTransactionSummary {
+            changes: diff_snapshots(&transaction.before, &transaction.after).len(),
+            id: transaction.id,
+            python: transaction.python,
+            operation: transaction.operation,
+            time: transaction.time,
+            exit_code: transaction.exit_code,
+        })
+        .collect())
+}
+
+/// Get exactly which packages a transaction changed
+pub fn diff_transaction(id: u32) -> Result<Vec<PackageChange>, SnapshotError> {
+    let transactions = load_transactions()?;
+    let transaction = transactions.iter().find(|transaction| transaction.id == id).ok_or(SnapshotError::NoTransaction(id))?;
+
+    Ok(diff_snapshots(&transaction.before, &transaction.after))
+}
+
+/// Restores the interpreter from a transaction to how it was before that transaction ran
+///
+/// Packages are installed at their old versions and anything that didn't exist yet is uninstalled.
+/// The rollback is recorded as it's own transaction, so it can be rolled back too. Returns pip's exit code.
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `id` - The id of the transaction to undo
+/// * `online` - If the internet can be reached, from `wheelhouse::is_online`
+pub fn rollback(window: &Window, id: u32, online: bool) -> Result<i32, SnapshotError> {
+    let transactions = load_transactions()?;
+    let transaction = transactions.iter().find(|transaction| transaction.id == id).ok_or(SnapshotError::NoTransaction(id))?;
+    let python = transaction.python.as_str();
+
+    let snapshot_path = get_content_folder().join(format!("python_snapshot_{id}.txt"));
+    write(&snapshot_path, transaction.before.join("\n"))?;
+    let snapshot_path = snapshot_path.to_string_lossy().to_string();
+
+    ccprintln!(window, "Rolling back '{}' in {python}", transaction.operation);
+
+    let exit_code = record(window, python, format!("rollback {id}"), || {
+        let current = match freeze(python) {
+            Ok(current) => current,
+            Err(e) => {
+                ccprintln!(window, "{e}");
+                return 1;
+            }
+        };
+
+        let added = diff_snapshots(&transaction.before, &current)
+            .into_iter()
+            .filter(|change| change.before.is_none())
+            .map(|change| change.name)
+            .collect::<Vec<_>>();
+
+        if !added.is_empty() {
+            let mut args = vec!["-m", "pip", "uninstall", "-y"];
+            args.extend(added.iter().map(String::as_str));
+
+            let exit_code = spawn_capture_process_and_get_exit_code(python, args);
+            if exit_code != 0 {
+                return exit_code;
+            }
+        }
+
+        wheelhouse::pip_install(window, python, &["-r", &snapshot_path], false, online)
+    });
+
+    // the snapshot file was only needed for pip
+    if let Err(e) = remove_file(&snapshot_path) {
+        ccprintln!(window, "Couldn't remove {snapshot_path}: {e}");
+    }
+
+    Ok(exit_code)
+}
@@ -0,0 +1,272 @@
+use super::interpreters::{InterpreterSource, PythonInterpreter, PYTHON_CONFIG_HEADER};
+use crate::{
+    bot_management::{cfg_helper::save_cfg, downloader::ProgressBarUpdate},
+    ccprintln,
+    commands::UPDATE_DOWNLOAD_PROGRESS_SIGNAL,
+    get_command_status, get_config_path, get_content_folder, load_gui_config, spawn_capture_process_and_get_exit_code, PYTHON_PATH,
+};
+use configparser::ini::Ini;
+use fs_extra::dir;
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
+use std::{
+    env::consts::{ARCH, OS},
+    fs::{create_dir_all, read_to_string, remove_file, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tauri::Window;
+use thiserror::Error;
+
+const STANDALONE_PYTHON_KEY: &str = "standalone_python";
+const STANDALONE_PYTHON_FOLDER: &str = "python-standalone";
+const STANDALONE_PYTHON_TARBALL: &str = "python-standalone.tar.gz";
+
+/// python-build-standalone publishes the SHA256 checksum of every build at the build's URL with this added to the end
+const CHECKSUM_EXTENSION: &str = ".sha256";
+
+/// The release of python-build-standalone that is downloaded when the user hasn't configured their own source
+const STANDALONE_PYTHON_RELEASE: &str = "20221002";
+const STANDALONE_PYTHON_VERSION: &str = "3.10.7";
+
+/// Used for progress updates if the server doesn't say how big the tarball is
+const STANDALONE_PYTHON_SIZE_ESTIMATE: u64 = 30_000_000;
+
+#[derive(Debug, Error)]
+pub enum StandalonePythonError {
+    #[error("There is no standalone Python build for {0}")]
+    UnsupportedPlatform(String),
+    #[error("Couldn't download the standalone Python tarball: {0}")]
+    Download(#[from] reqwest::Error),
+    #[error("Couldn't download the checksum of the standalone Python tarball from {0}: {1}")]
+    ChecksumDownload(String, reqwest::Error),
+    #[error("{0} doesn't contain a SHA256 checksum")]
+    BadChecksumFile(String),
+    #[error("The standalone Python tarball's SHA256 checksum is {actual}, but it should be {expected}")]
+    ChecksumMismatch { expected: String, actual: String },
+    #[error("There is already a Python environment at {0}, and it won't be replaced unless you confirm it")]
+    EnvExists(String),
+    #[error(transparent)]
+    EmitSignal(#[from] tauri::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Couldn't remove the old standalone Python: {0}")]
+    Remove(#[from] fs_extra::error::Error),
+    #[error("Couldn't extract the tarball, tar exited with code {0}")]
+    Extract(i32),
+    #[error("The extracted Python at {0} doesn't run")]
+    Verify(String),
+    #[error("The extracted Python is version {0}, but RLBot needs 3.7 through 3.10")]
+    UnsupportedVersion(String),
+    #[error("Failed to create virtual environment at {0}")]
+    Creation(String),
+}
+
+/// Get the URL of the default standalone Python build for the current OS & architecture
+fn default_standalone_python_url() -> Option<String> {
+    let os = match OS {
+        "linux" => "unknown-linux-gnu",
+        "macos" => "apple-darwin",
+        _ => return None,
+    };
+
+    if !["x86_64", "aarch64"].contains(&ARCH) {
+        return None;
+    }
+
+    Some(format!(
+        "https://github.com/indygreg/python-build-standalone/releases/download/{STANDALONE_PYTHON_RELEASE}/cpython-{STANDALONE_PYTHON_VERSION}+{STANDALONE_PYTHON_RELEASE}-{ARCH}-{os}-install_only.tar.gz"
+    ))
+}
+
+/// Load where the standalone Python should come from - either a URL or the path to a local tarball - from the GUI config
+pub fn load_standalone_python_source(conf: &Ini) -> Option<String> {
+    conf.get(PYTHON_CONFIG_HEADER, STANDALONE_PYTHON_KEY).filter(|source| !source.is_empty())
+}
+
+/// Save where the standalone Python should come from to the GUI config
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `source` - A URL or the path to a local tarball, or None to go back to the default download
+pub async fn save_standalone_python_source(window: &Window, source: Option<String>) {
+    let mut conf = load_gui_config(window).await;
+    conf.set(PYTHON_CONFIG_HEADER, STANDALONE_PYTHON_KEY, source);
+
+    if let Err(e) = save_cfg(&conf, get_config_path()).await {
+        ccprintln!(window, "Error saving standalone Python source: {e}");
+    }
+}
+
+/// Get the folder of the virtual environment that the standalone Python creates, which is also where the isolated Python is on Linux & MacOS
+pub fn get_env_folder() -> PathBuf {
+    get_content_folder().join("env")
+}
+
+/// Gets the hex digest at the start of a checksum file, which can be followed by the name of the file
+fn parse_checksum(contents: &str) -> Option<String> {
+    let digest = contents.split_whitespace().next()?.to_ascii_lowercase();
+    (digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())).then_some(digest)
+}
+
+/// Downloads the published checksum of the tarball at `url`
+async fn download_checksum(url: &str) -> Result<String, StandalonePythonError> {
+    let checksum_url = format!("{url}{CHECKSUM_EXTENSION}");
+    let contents = async {
+        let response = reqwest::get(&checksum_url).await?.error_for_status()?;
+        response.text().await
+    }
+    .await
+    .map_err(|e| StandalonePythonError::ChecksumDownload(checksum_url.clone(), e))?;
+
+    parse_checksum(&contents).ok_or(StandalonePythonError::BadChecksumFile(checksum_url))
+}
+
+/// Checks that the SHA256 checksum of the file at `path` is `expected`
+fn verify_checksum(path: &Path, expected: &str) -> Result<(), StandalonePythonError> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    let actual = format!("{:x}", hasher.finalize());
+
+    if actual != expected {
+        return Err(StandalonePythonError::ChecksumMismatch {
+            expected: expected.to_owned(),
+            actual,
+        });
+    }
+
+    Ok(())
+}
+
+/// Downloads the tarball at `url` to `file_path`, updating the user with continuous progress updates
+async fn download_tarball(window: &Window, url: &str, file_path: &Path) -> Result<(), StandalonePythonError> {
+    let res = reqwest::Client::new().get(url).send().await?.error_for_status()?;
+    let total_size = res.content_length().unwrap_or(STANDALONE_PYTHON_SIZE_ESTIMATE) as f64;
+    let mut stream = res.bytes_stream();
+    let mut bytes = Vec::with_capacity(total_size as usize);
+    let mut last_update = Instant::now();
+
+    while let Some(new_bytes) = stream.next().await {
+        bytes.extend_from_slice(&new_bytes?);
+
+        if last_update.elapsed().as_secs_f32() >= 0.1 {
+            let progress = bytes.len() as f64 / total_size * 100.0;
+            window.emit(UPDATE_DOWNLOAD_PROGRESS_SIGNAL, ProgressBarUpdate::new(progress, "Downloading tarball...".to_owned()))?;
+            last_update = Instant::now();
+        }
+    }
+
+    window.emit(UPDATE_DOWNLOAD_PROGRESS_SIGNAL, ProgressBarUpdate::new(100., "Writing tarball to disk...".to_owned()))?;
+    File::create(file_path)?.write_all(&bytes)?;
+
+    Ok(())
+}
+
+/// Installs a relocatable standalone `CPython` into the content folder, creates the `env` virtual environment from it, and sets `PYTHON_PATH`
+/// Updates the user with continuous progress updates.
+///
+/// FOR LINUX & MACOS - Windows uses the custom Python 3.7 zip instead
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `source` - A URL or the path to a local tarball, or None to download the default build for this platform
+/// * `replace_env` - If the user confirmed that an existing `env` virtual environment can be deleted
+pub async fn bootstrap_standalone_python(window: &Window, source: Option<String>, replace_env: bool) -> Result<(), StandalonePythonError> {
+    let content_folder = get_content_folder();
+    create_dir_all(&content_folder)?;
+
+    // checked before anything is downloaded, so nothing is wasted if the user doesn't want to replace it
+    let venv_folder = get_env_folder();
+    if venv_folder.exists() && !replace_env {
+        return Err(StandalonePythonError::EnvExists(venv_folder.to_string_lossy().to_string()));
+    }
+
+    let tarball = match source {
+        Some(source) if Path::new(&source).is_file() => {
+            let tarball = Path::new(&source).to_path_buf();
+
+            // a local tarball is only checked if a checksum file was put next to it
+            let checksum_file = format!("{source}{CHECKSUM_EXTENSION}");
+            match read_to_string(&checksum_file) {
+                Ok(contents) => verify_checksum(&tarball, &parse_checksum(&contents).ok_or(StandalonePythonError::BadChecksumFile(checksum_file))?)?,
+                Err(_) => ccprintln!(window, "Warning: there's no {checksum_file}, so {source} can't be verified"),
+            }
+
+            tarball
+        }
+        source => {
+            let url = source
+                .or_else(default_standalone_python_url)
+                .ok_or_else(|| StandalonePythonError::UnsupportedPlatform(format!("{OS} {ARCH}")))?;
+            let file_path = content_folder.join(STANDALONE_PYTHON_TARBALL);
+            let checksum = download_checksum(&url).await?;
+
+            ccprintln!(window, "Downloading standalone Python from {url}");
+            download_tarball(window, &url, &file_path).await?;
+
+            window.emit(UPDATE_DOWNLOAD_PROGRESS_SIGNAL, ProgressBarUpdate::new(100., "Verifying tarball...".to_owned()))?;
+            if let Err(e) = verify_checksum(&file_path, &checksum) {
+                remove_file(&file_path)?;
+                return Err(e);
+            }
+
+            file_path
+        }
+    };
+
+    window.emit(UPDATE_DOWNLOAD_PROGRESS_SIGNAL, ProgressBarUpdate::new(100., "Extracting tarball...".to_owned()))?;
+
+    let folder_destination = content_folder.join(STANDALONE_PYTHON_FOLDER);
+    if folder_destination.exists() {
+        dir::remove(&folder_destination)?;
+    }
+    create_dir_all(&folder_destination)?;
+
+    let tarball_str = tarball.to_string_lossy().to_string();
+    let folder_destination_str = folder_destination.to_string_lossy().to_string();
+    let exit_code = spawn_capture_process_and_get_exit_code("tar", ["-xzf", &tarball_str, "-C", &folder_destination_str]);
+    if exit_code != 0 {
+        return Err(StandalonePythonError::Extract(exit_code));
+    }
+
+    window.emit(UPDATE_DOWNLOAD_PROGRESS_SIGNAL, ProgressBarUpdate::new(100., "Verifying Python...".to_owned()))?;
+
+    // python-build-standalone's install_only builds put everything in a top-level "python" folder
+    let standalone_python = folder_destination.join("python/bin/python3").to_string_lossy().to_string();
+    let interpreter = PythonInterpreter::probe(&standalone_python, InterpreterSource::Rlbot).ok_or_else(|| StandalonePythonError::Verify(standalone_python.clone()))?;
+
+    let minor = interpreter
+        .version
+        .strip_prefix("3.")
+        .and_then(|rest| rest.split('.').next())
+        .and_then(|minor| minor.parse::<u8>().ok());
+    if !matches!(minor, Some(7..=10)) {
+        return Err(StandalonePythonError::UnsupportedVersion(interpreter.version));
+    }
+
+    ccprintln!(window, "Installed standalone Python {} to {folder_destination_str}", interpreter.version);
+    window.emit(UPDATE_DOWNLOAD_PROGRESS_SIGNAL, ProgressBarUpdate::new(100., "Creating virtual environment...".to_owned()))?;
+
+    if venv_folder.exists() {
+        ccprintln!(window, "Replacing the Python environment at {}", venv_folder.to_string_lossy());
+        dir::remove(&venv_folder)?;
+    }
+
+    let venv_folder_str = venv_folder.to_string_lossy().to_string();
+    if !get_command_status(&standalone_python, ["-m", "venv", &venv_folder_str]) {
+        return Err(StandalonePythonError::Creation(venv_folder_str));
+    }
+
+    let python_path = venv_folder.join("bin/python").to_string_lossy().to_string();
+    if !get_command_status(&python_path, ["--version"]) {
+        return Err(StandalonePythonError::Creation(venv_folder_str));
+    }
+
+    // Update the Python path
+    *PYTHON_PATH.write().await = python_path;
+
+    Ok(())
+}
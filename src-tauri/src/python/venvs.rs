@@ -0,0 +1,300 @@
+use super::packages;
+use crate::{
+    ccprintln, get_command_status, get_content_folder, load_gui_config,
+    rlbot::{
+        agents::runnable::Runnable,
+        parsing::bot_config_bundle::{BotConfigBundle, RLBotCfgParseError},
+    },
+    spawn_capture_process_and_get_exit_code,
+};
+use fs_extra::dir;
+use once_cell::sync::Lazy;
+use sanitize_filename::sanitize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tauri::Window;
+use thiserror::Error;
+use tokio::{fs as async_fs, sync::Mutex as AsyncMutex};
+
+const BOT_VENVS_FOLDER: &str = "bot_venvs";
+const REGISTRY_FILE: &str = "registry.json";
+
+/// The packages that the match handler needs, since it runs the bots from inside of their virtual environments
+const MATCH_HANDLER_PACKAGES: [&str; 2] = ["rlbot", "rlbot_smh"];
+
+/// Held while a virtual environment or the registry is being changed, so a bot that's added twice doesn't get two virtual environments built at once
+static VENV_LOCK: Lazy<AsyncMutex<()>> = Lazy::new(|| AsyncMutex::new(()));
+
+#[cfg(windows)]
+const VENV_PYTHON: &str = "Scripts/python.exe";
+#[cfg(not(windows))]
+const VENV_PYTHON: &str = "bin/python";
+
+/// A virtual environment that the GUI created for a single bot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotVenv {
+    /// The name of the bot, for display
+    pub name: String,
+    /// The folder that the virtual environment lives in
+    pub path: String,
+    /// The Python interpreter inside of the virtual environment
+    pub python: String,
+    /// The interpreter that the virtual environment was created from
+    pub base_python: String,
+    pub requirements_file: Option<String>,
+    /// A hash of the requirements file as it was when it was installed, so changes to it can be picked up
+    #[serde(default)]
+    pub requirements_hash: Option<String>,
+    /// The size of the virtual environment on disk, in bytes, as of the last time it was checked
+    pub size: u64,
+    /// When the virtual environment was created, in seconds since the Unix epoch
+    pub created: u64,
+    /// When a match was last started with the virtual environment, in seconds since the Unix epoch
+    pub last_used: Option<u64>,
+}
+
+/// All of the bot virtual environments, keyed by the path to the bot's config file
+pub type BotVenvRegistry = HashMap<String, BotVenv>;
+
+#[derive(Debug, Error)]
+pub enum BotVenvError {
+    #[error("I/O error when managing bot virtual environments: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Bot virtual environment registry is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Failed to load bot config: {0}")]
+    LoadCfg(#[from] RLBotCfgParseError),
+    #[error("{0} doesn't use a virtual environment")]
+    NotVirtual(String),
+    #[error("Failed to create virtual environment at {0}")]
+    Creation(String),
+    #[error("Failed to install requirements into the virtual environment, pip exited with code {0}")]
+    Requirements(i32),
+    #[error("Failed to install RLBot into the virtual environment, pip exited with code {0}")]
+    RLBot(i32),
+    #[error("There is no virtual environment for {0}")]
+    NoVenv(String),
+    #[error("Couldn't remove the virtual environment: {0}")]
+    Remove(#[from] fs_extra::error::Error),
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+}
+
+fn get_bot_venvs_folder() -> PathBuf {
+    get_content_folder().join(BOT_VENVS_FOLDER)
+}
+
+/// Get the folder where the virtual environment for the bot with the given name & config should go
+///
+/// A hash of the config path is included so two bots with the same name don't share a virtual environment,
+/// and it has to stay the same between versions of the GUI so existing virtual environments are found again
+fn get_venv_folder(name: &str, config_path: &str) -> PathBuf {
+    let canonical = Path::new(config_path).canonicalize().unwrap_or_else(|_| PathBuf::from(config_path));
+    let hash = format!("{:x}", Sha256::digest(canonical.to_string_lossy().as_bytes()));
+
+    get_bot_venvs_folder().join(format!("{}-{}", sanitize(name), &hash[..16]))
+}
+
+/// Hashes the contents of a requirements file, returning None if the bot doesn't have one or it can't be read
+async fn hash_requirements(requirements_file: Option<&str>) -> Option<String> {
+    let contents = async_fs::read(requirements_file?).await.ok()?;
+    Some(format!("{:x}", Sha256::digest(contents)))
+}
+
+/// Loads the registry of bot virtual environments, returning an empty registry if there isn't one yet
+pub async fn load_registry() -> Result<BotVenvRegistry, BotVenvError> {
+    let path = get_bot_venvs_folder().join(REGISTRY_FILE);
+    if !path.exists() {
+        return Ok(BotVenvRegistry::new());
+    }
+
+    Ok(serde_json::from_str(&async_fs::read_to_string(path).await?)?)
+}
+
+async fn save_registry(registry: &BotVenvRegistry) -> Result<(), BotVenvError> {
+    let folder = get_bot_venvs_folder();
+    async_fs::create_dir_all(&folder).await?;
+    async_fs::write(folder.join(REGISTRY_FILE), serde_json::to_string_pretty(registry)?).await?;
+
+    Ok(())
+}
+
+/// Creates a virtual environment for the bot with the given config and installs it's requirements file into it
+///
+/// Any existing virtual environment for the bot is replaced
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `config_path` - The path to the bot's config file
+/// * `base_python` - The interpreter to create the virtual environment from
+pub async fn create_bot_venv(window: &Window, config_path: &str, base_python: &str) -> Result<BotVenv, BotVenvError> {
+    let _lock = VENV_LOCK.lock().await;
+    create_bot_venv_locked(window, config_path, base_python).await
+}
+
+async fn create_bot_venv_locked(window: &Window, config_path: &str, base_python: &str) -> Result<BotVenv, BotVenvError> {
+    let bundle = BotConfigBundle::minimal_from_path(config_path).await?;
+    if !bundle.use_virtual_environment() {
+        return Err(BotVenvError::NotVirtual(bundle.name));
+    }
+
+    // rebuilding reuses the folder of the existing virtual environment
+    let mut registry = load_registry().await?;
+    let folder = registry
+        .get(config_path)
+        .map_or_else(|| get_venv_folder(&bundle.name, config_path), |venv| PathBuf::from(&venv.path));
+    if folder.exists() {
+        dir::remove(&folder)?;
+    }
+
+    let folder_str = folder.to_string_lossy().to_string();
+    ccprintln!(window, "Creating virtual environment for {} at {folder_str}", bundle.name);
+    if spawn_capture_process_and_get_exit_code(base_python, ["-m", "venv", &folder_str]) != 0 {
+        return Err(BotVenvError::Creation(folder_str));
+    }
+
+    let python = folder.join(VENV_PYTHON).to_string_lossy().to_string();
+    if !get_command_status(&python, ["--version"]) {
+        return Err(BotVenvError::Creation(folder_str));
+    }
+
+    if let Some(requirements_file) = bundle.get_requirements_file() {
+        ccprintln!(window, "Installing {requirements_file} into the virtual environment for {}", bundle.name);
+        let exit_code = spawn_capture_process_and_get_exit_code(&python, ["-m", "pip", "install", "--no-warn-script-location", "-r", requirements_file]);
+        if exit_code != 0 {
+            return Err(BotVenvError::Requirements(exit_code));
+        }
+    }
+
+    let core_packages = packages::load_core_packages(&load_gui_config(window).await);
+    let mut install_rlbot = vec!["-m", "pip", "install", "--no-warn-script-location"];
+    let rlbot_packages = MATCH_HANDLER_PACKAGES.map(|package| packages::get_package_name(&core_packages, package));
+    install_rlbot.extend(rlbot_packages.iter().map(String::as_str));
+
+    ccprintln!(window, "Installing RLBot into the virtual environment for {}", bundle.name);
+    let exit_code = spawn_capture_process_and_get_exit_code(&python, install_rlbot);
+    if exit_code != 0 {
+        return Err(BotVenvError::RLBot(exit_code));
+    }
+
+    let venv = BotVenv {
+        name: bundle.name.clone(),
+        size: dir::get_size(&folder).unwrap_or_default(),
+        path: folder_str,
+        python,
+        base_python: base_python.to_owned(),
+        requirements_file: bundle.get_requirements_file().clone(),
+        requirements_hash: hash_requirements(bundle.get_requirements_file().as_deref()).await,
+        created: now(),
+        last_used: None,
+    };
+
+    registry.insert(config_path.to_owned(), venv.clone());
+    save_registry(&registry).await?;
+
+    ccprintln!(window, "Finished creating the virtual environment for {}", bundle.name);
+
+    Ok(venv)
+}
+
+/// Makes sure that a bot which uses a virtual environment has an up-to-date one,
+/// creating it if it doesn't exist yet and rebuilding it if it's interpreter is gone or it's requirements file changed
+///
+/// Returns None if the bot doesn't use a virtual environment
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `config_path` - The path to the bot's config file
+/// * `default_python` - The interpreter to create the virtual environment from, unless an out-of-date one was created from another interpreter that still runs
+pub async fn ensure_bot_venv(window: &Window, config_path: &str, default_python: &str) -> Result<Option<BotVenv>, BotVenvError> {
+    let bundle = BotConfigBundle::minimal_from_path(config_path).await?;
+    if !bundle.use_virtual_environment() {
+        return Ok(None);
+    }
+
+    let _lock = VENV_LOCK.lock().await;
+    let mut base_python = default_python.to_owned();
+
+    if let Some(venv) = load_registry().await?.remove(config_path) {
+        let requirements_hash = hash_requirements(bundle.get_requirements_file().as_deref()).await;
+        if Path::new(&venv.python).exists() && venv.requirements_file == *bundle.get_requirements_file() && venv.requirements_hash == requirements_hash {
+            return Ok(Some(venv));
+        }
+
+        ccprintln!(window, "The virtual environment for {} is out of date, rebuilding it", bundle.name);
+        if get_command_status(&venv.base_python, ["--version"]) {
+            base_python = venv.base_python;
+        }
+    }
+
+    create_bot_venv_locked(window, config_path, &base_python).await.map(Some)
+}
+
+/// Deletes the virtual environment for the bot with the given config and removes it from the registry
+///
+/// # Arguments
+///
+/// * `config_path` - The path to the bot's config file
+pub async fn delete_bot_venv(config_path: &str) -> Result<(), BotVenvError> {
+    let _lock = VENV_LOCK.lock().await;
+    let mut registry = load_registry().await?;
+    let venv = registry.remove(config_path).ok_or_else(|| BotVenvError::NoVenv(config_path.to_owned()))?;
+
+    let folder = PathBuf::from(&venv.path);
+    if folder.exists() {
+        dir::remove(folder)?;
+    }
+
+    save_registry(&registry).await
+}
+
+/// Loads the registry with up-to-date sizes, dropping any virtual environments that were deleted outside of the GUI
+pub async fn refresh_registry() -> Result<BotVenvRegistry, BotVenvError> {
+    let _lock = VENV_LOCK.lock().await;
+    let mut registry = load_registry().await?;
+    registry.retain(|_, venv| PathBuf::from(&venv.python).exists());
+
+    for venv in registry.values_mut() {
+        venv.size = dir::get_size(&venv.path).unwrap_or(venv.size);
+    }
+
+    save_registry(&registry).await?;
+
+    Ok(registry)
+}
+
+/// Get the virtual environment interpreters for the bots with the given config paths, marking each of them as used right now
+///
+/// Returns a map of config path to the interpreter in the bot's virtual environment
+///
+/// # Arguments
+///
+/// * `config_paths` - The config paths of the bots that are about to be started
+pub async fn use_bot_venvs<'a, I: IntoIterator<Item = &'a String>>(config_paths: I) -> Result<HashMap<String, String>, BotVenvError> {
+    let _lock = VENV_LOCK.lock().await;
+    let mut registry = load_registry().await?;
+    let mut pythons = HashMap::new();
+    let now = now();
+
+    for config_path in config_paths {
+        if let Some(venv) = registry.get_mut(config_path) {
+            venv.last_used = Some(now);
+            pythons.insert(config_path.clone(), venv.python.clone());
+        }
+    }
+
+    if !pythons.is_empty() {
+        save_registry(&registry).await?;
+    }
+
+    Ok(pythons)
+}
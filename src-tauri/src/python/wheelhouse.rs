@@ -0,0 +1,128 @@
+use crate::{ccprintln, get_content_folder, spawn_capture_process_and_get_exit_code};
+use std::{
+    fs::{copy, create_dir_all, read_dir},
+    path::{Path, PathBuf},
+};
+use tauri::Window;
+use thiserror::Error;
+
+const WHEELHOUSE_FOLDER: &str = "wheelhouse";
+
+/// The kinds of files that pip can install from with `--find-links`
+const DISTRIBUTION_EXTENSIONS: [&str; 4] = [".whl", ".tar.gz", ".zip", ".tar.bz2"];
+
+#[derive(Debug, Error)]
+pub enum WheelhouseError {
+    #[error("I/O error when copying the wheelhouse: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0} is not a folder")]
+    NotAFolder(String),
+}
+
+/// Get the path to the folder where wheels are saved for installing offline
+fn get_wheelhouse_folder() -> PathBuf {
+    get_content_folder().join(WHEELHOUSE_FOLDER)
+}
+
+fn is_distribution(path: &Path) -> bool {
+    let name = path.file_name().map(|name| name.to_string_lossy().to_ascii_lowercase()).unwrap_or_default();
+    DISTRIBUTION_EXTENSIONS.iter().any(|extension| name.ends_with(extension))
+}
+
+/// Checks if there is anything in the wheelhouse to install from
+pub fn has_wheels() -> bool {
+    read_dir(get_wheelhouse_folder()).map_or(false, |entries| entries.flatten().any(|entry| is_distribution(&entry.path())))
+}
+
+/// Checks if the internet can be reached, which decides between installing normally and installing from the wheelhouse
+pub async fn is_online() -> bool {
+    online::tokio::check(None).await.is_ok()
+}
+
+/// Installs packages with pip, either from the internet or from the wheelhouse
+///
+/// When online, everything that was installed is also built into the wheelhouse so it can be installed again later without internet.
+/// Returns pip's exit code.
+///
+/// # Arguments
+///
+/// * `window` - A reference to the GUI, obtained from a `#[tauri::command]` function
+/// * `python` - The interpreter to install the packages into
+/// * `packages` - What to install, which is either package specifiers or `["-r", "path/to/requirements.txt"]`
+/// * `upgrade` - If already installed packages should be upgraded
+/// * `online` - If the internet can be reached, from `is_online`
+pub fn pip_install(window: &Window, python: &str, packages: &[&str], upgrade: bool, online: bool) -> i32 {
+    let wheelhouse = get_wheelhouse_folder().to_string_lossy().to_string();
+
+    let mut args = vec!["-m", "pip", "install"];
+    if upgrade {
+        args.push("-U");
+    }
+    args.push("--no-warn-script-location");
+
+    if !online {
+        ccprintln!(window, "No internet connection, installing {} from {wheelhouse}", packages.join(" "));
+        args.extend(["--no-index", "--find-links", wheelhouse.as_str()]);
+        args.extend(packages);
+
+        return spawn_capture_process_and_get_exit_code(python, args);
+    }
+
+    args.extend(packages);
+    let exit_code = spawn_capture_process_and_get_exit_code(python, args);
+
+    if exit_code == 0 {
+        if let Err(e) = create_dir_all(&wheelhouse) {
+            ccprintln!(window, "Couldn't create the wheelhouse: {e}");
+            return exit_code;
+        }
+
+        // pip's cache is still warm from the install, so this is usually fast
+        let mut wheel_args = vec!["-m", "pip", "wheel", "--wheel-dir", wheelhouse.as_str()];
+        wheel_args.extend(packages);
+
+        if spawn_capture_process_and_get_exit_code(python, wheel_args) != 0 {
+            ccprintln!(window, "Couldn't save {} to the wheelhouse for offline installs", packages.join(" "));
+        }
+    }
+
+    exit_code
+}
+
+/// Copies every distribution file from one folder to another, returning how many were copied
+fn copy_distributions(from: &Path, to: &Path) -> Result<usize, WheelhouseError> {
+    if !from.is_dir() {
+        return Err(WheelhouseError::NotAFolder(from.to_string_lossy().to_string()));
+    }
+
+    create_dir_all(to)?;
+
+    let mut count = 0;
+    for entry in read_dir(from)?.flatten() {
+        let path = entry.path();
+        if is_distribution(&path) {
+            copy(&path, to.join(entry.file_name()))?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+/// Copies the wheelhouse to another folder, like a USB drive, so it can be imported on a machine without internet
+///
+/// # Arguments
+///
+/// * `destination` - The folder to copy the wheels into
+pub fn export_wheelhouse(destination: &str) -> Result<usize, WheelhouseError> {
+    copy_distributions(&get_wheelhouse_folder(), Path::new(destination))
+}
+
+/// Copies wheels from another folder into the wheelhouse
+///
+/// # Arguments
+///
+/// * `source` - The folder to copy the wheels from, usually one made with `export_wheelhouse`
+pub fn import_wheelhouse(source: &str) -> Result<usize, WheelhouseError> {
+    copy_distributions(Path::new(source), &get_wheelhouse_folder())
+}
@@ -1,4 +1,5 @@
 pub(crate) mod agents;
 pub(crate) mod gateway_util;
+pub(crate) mod loadout_generator;
 pub(crate) mod parsing;
 pub(crate) mod setup_manager;
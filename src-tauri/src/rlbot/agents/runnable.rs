@@ -1,12 +1,13 @@
-use std::ffi::OsStr;
-
+use crate::python::site_packages::InstalledPackages;
 use tauri::Window;
 
 pub trait Runnable {
     fn get_config_file_name(&self) -> &str;
+    fn get_config_path(&self) -> &str;
     fn get_requirements_file(&self) -> &Option<String>;
     fn use_virtual_environment(&self) -> bool;
-    fn get_missing_packages<S: AsRef<OsStr>>(&self, window: &Window, python: S) -> Vec<String>;
+    fn requires_tkinter(&self) -> bool;
+    fn get_missing_packages(&self, window: &Window, installed: &InstalledPackages) -> Vec<String>;
     fn logo(&self) -> &Option<String>;
     fn load_logo(&self) -> Option<String>;
     fn is_rlbot_controlled(&self) -> bool;
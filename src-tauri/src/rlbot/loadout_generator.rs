@@ -0,0 +1,116 @@
+use crate::{get_command, rlbot::parsing::agent_config_parser::BotLooksConfig};
+use std::{
+    io::Read,
+    process::Stdio,
+    thread,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// How long a loadout generator can take before it's killed, which is plenty for importing RLBot and picking a loadout
+const GENERATOR_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// How often the generator is checked on while waiting for it to finish
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Runs a bot's loadout generator the same way the RLBot framework does, printing the loadouts of both teams as JSON on the last line
+const LOADOUT_GENERATOR_SCRIPT: &str = r#"
+import json, sys
+from pathlib import Path
+from rlbot.agents.base_loadout_generator import BaseLoadoutGenerator
+from rlbot.utils.class_importer import import_class_with_base
+
+KEYS = ["team_color_id", "custom_color_id", "car_id", "decal_id", "wheels_id", "boost_id", "antenna_id", "hat_id", "paint_finish_id",
+        "custom_finish_id", "engine_audio_id", "trails_id", "goal_explosion_id", "primary_color_lookup", "secondary_color_lookup"]
+PAINT_KEYS = ["car_paint_id", "decal_paint_id", "wheels_paint_id", "boost_paint_id", "antenna_paint_id", "hat_paint_id", "trails_paint_id",
+              "goal_explosion_paint_id"]
+
+def to_str(value):
+    return "" if value is None else str(value)
+
+path = Path(sys.argv[1])
+index = int(sys.argv[2])
+generator = import_class_with_base(str(path), BaseLoadoutGenerator).get_loaded_class()(path.parent)
+
+looks = {}
+for team, name in ((0, "blue"), (1, "orange")):
+    loadout = generator.generate_loadout(index, team)
+    team_looks = {key: to_str(getattr(loadout, key, None)) for key in KEYS}
+    team_looks.update({key: to_str(getattr(loadout.paint_config, key, None)) for key in PAINT_KEYS})
+    looks[name] = team_looks
+
+print()
+print(json.dumps(looks))
+"#;
+
+#[derive(Debug, Error)]
+pub enum BotLoadoutError {
+    #[error("Couldn't run the loadout generator: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Loadout generator failed: {0}")]
+    Failed(String),
+    #[error("Loadout generator didn't finish within {} seconds", GENERATOR_TIMEOUT.as_secs())]
+    Timeout,
+    #[error("Loadout generator gave an invalid loadout: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Reads everything from a pipe of a child process on another thread, so the child never blocks on a full pipe while it's being waited on
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+/// Runs a bot's Python loadout generator for both teams
+///
+/// This blocks until the generator finishes or is killed after `GENERATOR_TIMEOUT`, so it shouldn't be called on the async runtime
+///
+/// # Arguments
+///
+/// * `python` - The Python interpreter that runs the bot
+/// * `generator_path` - The path to the bot's loadout generator file
+/// * `index` - The player index to generate the loadout for, which some generators use to give each copy of a bot a different car
+pub fn run(python: &str, generator_path: &str, index: u32) -> Result<BotLooksConfig, BotLoadoutError> {
+    let mut child = get_command(python, ["-c", LOADOUT_GENERATOR_SCRIPT, generator_path, &index.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let deadline = Instant::now() + GENERATOR_TIMEOUT;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if Instant::now() >= deadline {
+            // killing the generator closes its pipes, which lets the reading threads finish
+            child.kill()?;
+            child.wait()?;
+            return Err(BotLoadoutError::Timeout);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        // the last line of a Python traceback is the actual error
+        return Err(BotLoadoutError::Failed(stderr.trim().lines().last().unwrap_or_default().to_owned()));
+    }
+
+    // the generator might print things too, so only the last line is the loadout
+    let stdout = String::from_utf8_lossy(&stdout);
+    Ok(serde_json::from_str(stdout.trim().lines().last().unwrap_or_default())?)
+}
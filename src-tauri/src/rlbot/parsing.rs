@@ -1,4 +1,8 @@
 pub mod agent_config_parser;
+pub mod bakkesmod_code;
 pub mod bot_config_bundle;
+pub mod bot_index;
+pub mod config_linter;
 pub mod directory_scanner;
+pub mod item_catalog;
 pub mod match_settings_config_parser;
@@ -11,7 +11,7 @@ pub const BOT_CONFIG_LOADOUT_ORANGE_HEADER: &str = "Bot Loadout Orange";
 pub const BOT_CONFIG_LOADOUT_PAINT_BLUE_HEADER: &str = "Bot Paint Blue";
 pub const BOT_CONFIG_LOADOUT_PAINT_ORANGE_HEADER: &str = "Bot Paint Orange";
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
 pub struct BotTeamLooksConfig {
     pub team_color_id: String,
     pub custom_color_id: String,
@@ -136,7 +136,8 @@ impl BotLooksConfig {
     }
 
     pub async fn save_to_path(&self, window: &Window, path: &str) {
-        let mut config = Ini::new();
+        // the rest of the file is loaded too, so saving only changes the loadout
+        let mut config = load_cfg(path).await.unwrap_or_else(|_| Ini::new());
         self.blue.save_to_config(&mut config, BOT_CONFIG_LOADOUT_HEADER, BOT_CONFIG_LOADOUT_PAINT_BLUE_HEADER);
         self.orange
             .save_to_config(&mut config, BOT_CONFIG_LOADOUT_ORANGE_HEADER, BOT_CONFIG_LOADOUT_PAINT_ORANGE_HEADER);
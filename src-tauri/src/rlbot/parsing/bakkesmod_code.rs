@@ -0,0 +1,421 @@
+use super::{
+    agent_config_parser::{BotLooksConfig, BotTeamLooksConfig},
+    item_catalog::COLOR_PALETTES,
+};
+use thiserror::Error;
+
+/// The version of the code format that BakkesMod writes, which has 13 bit item IDs and custom colors
+const CODE_VERSION: u8 = 2;
+/// The version, size, and CRC
+const HEADER_BYTES: usize = 3;
+
+const VERSION_BITS: u32 = 6;
+const SIZE_BITS: u32 = 10;
+const CRC_BITS: u32 = 8;
+const ITEM_COUNT_BITS: u32 = 4;
+const SLOT_BITS: u32 = 5;
+const ID_BITS: u32 = 13;
+const PAINT_BITS: u32 = 6;
+const COLOR_BITS: u32 = 8;
+
+/// The biggest item ID that fits in a code, newer items can't be exported
+const MAX_ITEM_ID: u32 = (1 << ID_BITS) - 1;
+
+/// Rocket League's slot indexes, in the order BakkesMod writes them
+const BODY_SLOT: u32 = 0;
+const DECAL_SLOT: u32 = 1;
+const WHEELS_SLOT: u32 = 2;
+const BOOST_SLOT: u32 = 3;
+const ANTENNA_SLOT: u32 = 4;
+const HAT_SLOT: u32 = 5;
+const PAINT_FINISH_SLOT: u32 = 7;
+const CUSTOM_FINISH_SLOT: u32 = 12;
+const ENGINE_AUDIO_SLOT: u32 = 13;
+const TRAIL_SLOT: u32 = 14;
+const GOAL_EXPLOSION_SLOT: u32 = 15;
+
+#[derive(Debug, Error)]
+pub enum LoadoutCodeError {
+    #[error("Loadout code is not valid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("Loadout code is cut off")]
+    TooShort,
+    #[error("Loadout code is version {0}, but only version {CODE_VERSION} is supported")]
+    Version(u8),
+    #[error("Loadout code is corrupted (the checksum doesn't match)")]
+    Checksum,
+    #[error("{team} item {id} can't be put in a loadout code, BakkesMod only supports item IDs up to {MAX_ITEM_ID}")]
+    IdTooBig { team: &'static str, id: u32 },
+}
+
+/// Reads bits from the start of a buffer, least significant bit first
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit: usize,
+}
+
+impl<'a> BitReader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, bit: 0 }
+    }
+
+    fn read(&mut self, bits: u32) -> Result<u32, LoadoutCodeError> {
+        let mut value = 0;
+        for i in 0..bits {
+            let byte = self.bytes.get(self.bit / 8).ok_or(LoadoutCodeError::TooShort)?;
+            if byte >> (self.bit % 8) & 1 == 1 {
+                value |= 1 << i;
+            }
+            self.bit += 1;
+        }
+
+        Ok(value)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, LoadoutCodeError> {
+        Ok(self.read(1)? == 1)
+    }
+}
+
+/// Writes bits to a growing buffer, least significant bit first
+#[derive(Default, PartialEq, Eq)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        for i in 0..bits {
+            if self.bit % 8 == 0 {
+                self.bytes.push(0);
+            }
+            if value >> i & 1 == 1 {
+                self.bytes[self.bit / 8] |= 1 << (self.bit % 8);
+            }
+            self.bit += 1;
+        }
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write(u32::from(value), 1);
+    }
+
+    fn append(&mut self, other: &Self) {
+        let mut reader = BitReader::new(&other.bytes);
+        for _ in 0..other.bit {
+            // can't fail, the reader never goes past the bits that were written
+            self.write(reader.read(1).unwrap_or_default(), 1);
+        }
+    }
+}
+
+/// BakkesMod's checksum of the bytes after the header
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0xFF, |crc, byte| crc ^ byte)
+}
+
+/// Gets the item ID and paint ID fields of a team's loadout that go in each slot
+fn slots(looks: &mut BotTeamLooksConfig) -> [(u32, &mut String, Option<&mut String>); 11] {
+    [
+        (BODY_SLOT, &mut looks.car_id, Some(&mut looks.car_paint_id)),
+        (DECAL_SLOT, &mut looks.decal_id, Some(&mut looks.decal_paint_id)),
+        (WHEELS_SLOT, &mut looks.wheels_id, Some(&mut looks.wheels_paint_id)),
+        (BOOST_SLOT, &mut looks.boost_id, Some(&mut looks.boost_paint_id)),
+        (ANTENNA_SLOT, &mut looks.antenna_id, Some(&mut looks.antenna_paint_id)),
+        (HAT_SLOT, &mut looks.hat_id, Some(&mut looks.hat_paint_id)),
+        (PAINT_FINISH_SLOT, &mut looks.paint_finish_id, None),
+        (CUSTOM_FINISH_SLOT, &mut looks.custom_finish_id, None),
+        (ENGINE_AUDIO_SLOT, &mut looks.engine_audio_id, None),
+        (TRAIL_SLOT, &mut looks.trails_id, Some(&mut looks.trails_paint_id)),
+        (GOAL_EXPLOSION_SLOT, &mut looks.goal_explosion_id, Some(&mut looks.goal_explosion_paint_id)),
+    ]
+}
+
+/// Parses a color lookup like `[255, 128, 0]`
+fn parse_color_lookup(lookup: &str) -> Option<[u8; 3]> {
+    let mut channels = lookup.trim().strip_prefix('[')?.strip_suffix(']')?.split(',').map(|channel| channel.trim().parse().ok());
+    let color = [channels.next()??, channels.next()??, channels.next()??];
+
+    channels.next().is_none().then_some(color)
+}
+
+fn format_color_lookup([r, g, b]: [u8; 3]) -> String {
+    format!("[{r}, {g}, {b}]")
+}
+
+/// Gets the index of the color in the palette that is closest to the given color
+fn closest_color(palette: &[[u8; 3]], color: [u8; 3]) -> usize {
+    let distance = |other: &[u8; 3]| -> u32 { color.iter().zip(other).map(|(&a, &b)| u32::from(a.abs_diff(b)).pow(2)).sum() };
+
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, other)| distance(other))
+        .map(|(index, _)| index)
+        .unwrap_or_default()
+}
+
+/// Gets the exact colors of a team's loadout, from the color lookups if they're set and the palettes if they aren't
+fn team_colors(looks: &BotTeamLooksConfig, palette: &[[u8; 3]]) -> Option<([u8; 3], [u8; 3])> {
+    let from_palette = |palette: &[[u8; 3]], id: &str| id.trim().parse::<usize>().ok().and_then(|id| palette.get(id).copied());
+
+    let primary = parse_color_lookup(&looks.primary_color_lookup).or_else(|| from_palette(palette, &looks.team_color_id))?;
+    let secondary = parse_color_lookup(&looks.secondary_color_lookup).or_else(|| from_palette(&COLOR_PALETTES.secondary, &looks.custom_color_id))?;
+
+    Some((primary, secondary))
+}
+
+fn write_team(team: &'static str, looks: &BotTeamLooksConfig, palette: &[[u8; 3]]) -> Result<BitWriter, LoadoutCodeError> {
+    let mut looks = looks.clone();
+    let items = slots(&mut looks)
+        .into_iter()
+        .filter_map(|(slot, id, paint)| {
+            let id = id.trim().parse::<u32>().ok().filter(|&id| id != 0)?;
+            let paint = paint.and_then(|paint| paint.trim().parse::<u32>().ok()).unwrap_or_default();
+            Some((slot, id, paint))
+        })
+        .collect::<Vec<_>>();
+
+    let mut writer = BitWriter::default();
+    writer.write(items.len() as u32, ITEM_COUNT_BITS);
+    for (slot, id, paint) in items {
+        if id > MAX_ITEM_ID {
+            return Err(LoadoutCodeError::IdTooBig { team, id });
+        }
+
+        writer.write(slot, SLOT_BITS);
+        writer.write(id, ID_BITS);
+        writer.write_bool(paint > 0);
+        if paint > 0 {
+            writer.write(paint, PAINT_BITS);
+        }
+    }
+
+    let colors = team_colors(&looks, palette);
+    writer.write_bool(colors.is_some());
+    if let Some((primary, secondary)) = colors {
+        for channel in primary.into_iter().chain(secondary) {
+            writer.write(u32::from(channel), COLOR_BITS);
+        }
+    }
+
+    Ok(writer)
+}
+
+fn read_color(reader: &mut BitReader) -> Result<[u8; 3], LoadoutCodeError> {
+    // the values are read 8 bits at a time, so they always fit
+    Ok([reader.read(COLOR_BITS)? as u8, reader.read(COLOR_BITS)? as u8, reader.read(COLOR_BITS)? as u8])
+}
+
+fn read_team(reader: &mut BitReader, palette: &[[u8; 3]]) -> Result<BotTeamLooksConfig, LoadoutCodeError> {
+    let mut looks = BotTeamLooksConfig::default();
+
+    for _ in 0..reader.read(ITEM_COUNT_BITS)? {
+        let slot = reader.read(SLOT_BITS)?;
+        let id = reader.read(ID_BITS)?;
+        let paint = if reader.read_bool()? { reader.read(PAINT_BITS)? } else { 0 };
+
+        // items in slots that bots can't use, like player banners, are skipped
+        if let Some((_, id_field, paint_field)) = slots(&mut looks).into_iter().find(|(index, _, _)| *index == slot) {
+            *id_field = id.to_string();
+            if let Some(paint_field) = paint_field {
+                *paint_field = paint.to_string();
+            }
+        }
+    }
+
+    if reader.read_bool()? {
+        let primary = read_color(reader)?;
+        let secondary = read_color(reader)?;
+
+        looks.team_color_id = closest_color(palette, primary).to_string();
+        looks.custom_color_id = closest_color(&COLOR_PALETTES.secondary, secondary).to_string();
+        looks.primary_color_lookup = format_color_lookup(primary);
+        looks.secondary_color_lookup = format_color_lookup(secondary);
+    }
+
+    Ok(looks)
+}
+
+/// Converts both teams' loadouts into a BakkesMod loadout code
+///
+/// Items that aren't set or are 0 are left out, and colors are taken from the color lookups if they're set
+pub fn encode(looks: &BotLooksConfig) -> Result<String, LoadoutCodeError> {
+    let blue = write_team("Blue", &looks.blue, &COLOR_PALETTES.blue)?;
+    let orange = write_team("Orange", &looks.orange, &COLOR_PALETTES.orange)?;
+    let blue_is_orange = blue == orange;
+
+    let mut writer = BitWriter::default();
+    writer.write(u32::from(CODE_VERSION), VERSION_BITS);
+    // the size and checksum are filled in at the end
+    writer.write(0, SIZE_BITS + CRC_BITS);
+    writer.write_bool(blue_is_orange);
+    writer.append(&blue);
+    if !blue_is_orange {
+        writer.append(&orange);
+    }
+
+    let mut bytes = writer.bytes;
+    let size = bytes.len();
+    let crc = checksum(&bytes[HEADER_BYTES..]);
+
+    // the size takes up the top 2 bits of the first byte and all of the second, the checksum is the third byte
+    bytes[0] |= ((size & 0b11) as u8) << VERSION_BITS;
+    bytes[1] = (size >> 2) as u8;
+    bytes[2] = crc;
+
+    Ok(base64::encode(bytes))
+}
+
+/// Converts a BakkesMod loadout code into both teams' loadouts
+///
+/// Colors are converted to the closest colors in the palettes, and the exact colors are kept in the color lookups
+pub fn decode(code: &str) -> Result<BotLooksConfig, LoadoutCodeError> {
+    let bytes = base64::decode(code.trim())?;
+    let mut reader = BitReader::new(&bytes);
+
+    let version = reader.read(VERSION_BITS)? as u8;
+    if version != CODE_VERSION {
+        return Err(LoadoutCodeError::Version(version));
+    }
+
+    let size = reader.read(SIZE_BITS)? as usize;
+    let crc = reader.read(CRC_BITS)? as u8;
+    if size < HEADER_BYTES || size > bytes.len() {
+        return Err(LoadoutCodeError::TooShort);
+    }
+
+    if checksum(&bytes[HEADER_BYTES..size]) != crc {
+        return Err(LoadoutCodeError::Checksum);
+    }
+
+    let blue_is_orange = reader.read_bool()?;
+    let blue = read_team(&mut reader, &COLOR_PALETTES.blue)?;
+    let orange = if blue_is_orange {
+        // the orange team has the same items and exact colors, but it's palette is different
+        let mut orange = blue.clone();
+        if let Some(primary) = parse_color_lookup(&blue.primary_color_lookup) {
+            orange.team_color_id = closest_color(&COLOR_PALETTES.orange, primary).to_string();
+        }
+        orange
+    } else {
+        read_team(&mut reader, &COLOR_PALETTES.orange)?
+    };
+
+    Ok(BotLooksConfig { blue, orange })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Codes written bit by bit from BakkesMod's version 2 layout, independently of `encode`
+    const SEPARATE_TEAMS_CODE: &str = "wgaZCFwAAi8sSADw4M4m1F/O/+efgMkwGCAA";
+    const BLUE_IS_ORANGE_CODE: &str = "AgR0BUwxz1Ed5vz9lJeXAw==";
+
+    fn separate_teams_looks() -> BotLooksConfig {
+        BotLooksConfig {
+            blue: BotTeamLooksConfig {
+                team_color_id: "0".to_owned(),
+                custom_color_id: "2".to_owned(),
+                primary_color_lookup: "[80, 127, 57]".to_owned(),
+                secondary_color_lookup: "[255, 159, 127]".to_owned(),
+                car_id: "23".to_owned(),
+                car_paint_id: "0".to_owned(),
+                wheels_id: "376".to_owned(),
+                wheels_paint_id: "5".to_owned(),
+                antenna_id: "1".to_owned(),
+                antenna_paint_id: "0".to_owned(),
+                goal_explosion_id: "1904".to_owned(),
+                goal_explosion_paint_id: "13".to_owned(),
+                ..Default::default()
+            },
+            orange: BotTeamLooksConfig {
+                car_id: "403".to_owned(),
+                car_paint_id: "1".to_owned(),
+                boost_id: "32".to_owned(),
+                boost_paint_id: "0".to_owned(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn blue_is_orange_looks() -> BotLooksConfig {
+        let blue = BotTeamLooksConfig {
+            team_color_id: "1".to_owned(),
+            custom_color_id: "0".to_owned(),
+            primary_color_lookup: "[57, 127, 63]".to_owned(),
+            secondary_color_lookup: "[229, 229, 229]".to_owned(),
+            car_id: "3155".to_owned(),
+            car_paint_id: "0".to_owned(),
+            goal_explosion_id: "2702".to_owned(),
+            goal_explosion_paint_id: "3".to_owned(),
+            ..Default::default()
+        };
+
+        BotLooksConfig {
+            orange: BotTeamLooksConfig {
+                team_color_id: "0".to_owned(),
+                ..blue.clone()
+            },
+            blue,
+        }
+    }
+
+    #[test]
+    fn decode_separate_teams() {
+        assert_eq!(decode(SEPARATE_TEAMS_CODE).unwrap(), separate_teams_looks());
+    }
+
+    #[test]
+    fn encode_separate_teams() {
+        assert_eq!(encode(&separate_teams_looks()).unwrap(), SEPARATE_TEAMS_CODE);
+    }
+
+    #[test]
+    fn decode_blue_is_orange() {
+        assert_eq!(decode(BLUE_IS_ORANGE_CODE).unwrap(), blue_is_orange_looks());
+    }
+
+    #[test]
+    fn encode_blue_is_orange() {
+        assert_eq!(encode(&blue_is_orange_looks()).unwrap(), BLUE_IS_ORANGE_CODE);
+    }
+
+    #[test]
+    fn round_trip() {
+        for code in [SEPARATE_TEAMS_CODE, BLUE_IS_ORANGE_CODE] {
+            assert_eq!(encode(&decode(code).unwrap()).unwrap(), code);
+        }
+    }
+
+    #[test]
+    fn bad_checksum() {
+        let mut bytes = base64::decode(SEPARATE_TEAMS_CODE).unwrap();
+        bytes[HEADER_BYTES] ^= 1;
+
+        assert!(matches!(decode(&base64::encode(bytes)), Err(LoadoutCodeError::Checksum)));
+    }
+
+    #[test]
+    fn bad_version() {
+        let mut bytes = base64::decode(SEPARATE_TEAMS_CODE).unwrap();
+        bytes[0] = (bytes[0] & !0b11_1111) | 3;
+
+        assert!(matches!(decode(&base64::encode(bytes)), Err(LoadoutCodeError::Version(3))));
+    }
+
+    #[test]
+    fn cut_off() {
+        assert!(matches!(decode(&SEPARATE_TEAMS_CODE[..8]), Err(LoadoutCodeError::TooShort)));
+    }
+
+    #[test]
+    fn id_too_big() {
+        let mut looks = separate_teams_looks();
+        looks.orange.goal_explosion_id = (MAX_ITEM_ID + 1).to_string();
+
+        assert!(matches!(encode(&looks), Err(LoadoutCodeError::IdTooBig { team: "Orange", id: 8192 })));
+    }
+}
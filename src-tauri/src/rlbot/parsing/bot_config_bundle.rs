@@ -1,23 +1,15 @@
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
-
 use crate::{
-    bot_management::cfg_helper::{load_cfg, load_cfg_sync, Error},
-    ccprintln, get_command_status,
+    bot_management::{
+        cfg_helper::{load_cfg, load_cfg_sync, Error},
+        logo_cache::{thumbnail_url, LogoSize},
+    },
+    ccprintln,
+    python::site_packages::{self, InstalledPackages},
     rlbot::agents::{base_script::SCRIPT_FILE_KEY, runnable::Runnable},
 };
 use configparser::ini::Ini;
-use imghdr::Type;
 use serde::{Deserialize, Serialize};
-use std::{
-    borrow::ToOwned,
-    ffi::OsStr,
-    fs,
-    io::Read,
-    path::Path,
-    process::{self, Stdio},
-    str::from_utf8,
-};
+use std::{borrow::ToOwned, path::Path};
 use tauri::Window;
 use thiserror::Error;
 
@@ -25,17 +17,16 @@ pub const PYTHON_FILE_KEY: &str = "python_file";
 pub const REQUIREMENTS_FILE_KEY: &str = "requirements_file";
 pub const LOGO_FILE_KEY: &str = "logo_file";
 pub const NAME_KEY: &str = "name";
-// pub const SUPPORTS_EARLY_START_KEY: &str = "supports_early_start";
 pub const REQUIRES_TKINTER: &str = "requires_tkinter";
 pub const USE_VIRTUAL_ENVIRONMENT_KEY: &str = "use_virtual_environment";
 
 pub const BOT_CONFIG_MODULE_HEADER: &str = "Locations";
 pub const BOT_CONFIG_DETAILS_HEADER: &str = "Details";
 pub const SUPPORTS_STANDALONE: &str = "supports_standalone";
-// pub const LOADOUT_GENERATOR_FILE_KEY: &str = "loadout_generator";
+pub const LOADOUT_GENERATOR_FILE_KEY: &str = "loadout_generator";
 pub const LOOKS_CONFIG_KEY: &str = "looks_config";
-// pub const SUPPORTS_EARLY_START_KEY: &str = "supports_early_start";
-// pub const MAXIMUM_TICK_RATE_PREFERENCE_KEY: &str = "maximum_tick_rate_preference";
+pub const SUPPORTS_EARLY_START_KEY: &str = "supports_early_start";
+pub const MAXIMUM_TICK_RATE_PREFERENCE_KEY: &str = "maximum_tick_rate_preference";
 
 pub const BOT_CONFIG_PARAMS_HEADER: &str = "Bot Parameters";
 pub const EXECUTABLE_PATH_KEY: &str = "path";
@@ -75,57 +66,6 @@ impl DevInfo {
     }
 }
 
-fn get_file_extension(vec: &[u8]) -> Option<&'static str> {
-    match imghdr::from_bytes(vec) {
-        // Gif 87a and 89a Files
-        Some(Type::Gif) => Some("gif"),
-        // TIFF files
-        Some(Type::Tiff) => Some("tiff"),
-        // Sun Raster files
-        Some(Type::Rast) => Some("ras"),
-        // X Bitmap files
-        Some(Type::Xbm) => Some("xbm"),
-        // JPEG data in JFIF or Exif formats
-        Some(Type::Jpeg) => Some("jpeg"),
-        // BMP files
-        Some(Type::Bmp) => Some("bmp"),
-        // Portable Network Graphics
-        Some(Type::Png) => Some("png"),
-        // WebP files
-        Some(Type::Webp) => Some("webp"),
-        // OpenEXR files
-        Some(Type::Exr) => Some("exr"),
-        // BGP (Better Portable Graphics) files
-        Some(Type::Bgp) => Some("bgp"),
-        // PBM (Portable bitmap) files
-        Some(Type::Pbm) => Some("pbm"),
-        // PGM (Portable graymap) files
-        Some(Type::Pgm) => Some("pgm"),
-        // PPM (Portable pixmap) files
-        Some(Type::Ppm) => Some("ppm"),
-        // SGI image library files
-        Some(Type::Rgb) => Some("rgb"),
-        // HDR files (RGBE)
-        Some(Type::Rgbe) => Some("hdr"),
-        // FLIF (Free Lossless Image Format) files
-        Some(Type::Flif) => Some("flif"),
-        // ICO files
-        Some(Type::Ico) => Some("ico"),
-        None => None,
-    }
-}
-
-pub fn to_base64(path: &str) -> Option<String> {
-    let Ok(file) = &mut fs::File::open(path) else {
-        return None;
-    };
-
-    let mut vec = Vec::new();
-    file.read_to_end(&mut vec).ok()?;
-
-    get_file_extension(&vec).map(|extension| format!("data:image/{extension};base64,{}", base64::encode(vec).replace("\r\n", "")))
-}
-
 #[derive(Debug, Error)]
 pub enum RLBotCfgParseError {
     #[error(transparent)]
@@ -136,10 +76,139 @@ pub enum RLBotCfgParseError {
     NoLooksConfig(String),
     #[error("No python file found in config file {0}")]
     NoPythonFile(String),
+    #[error("Executable {1} from config file {0} does not exist")]
+    NoExecutableFile(String, String),
     #[error("No script file found in config file {0}")]
     NoScriptFile(String),
 }
 
+/// How RLBot runs a bot
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BotRuntime {
+    /// A Python agent loaded from `python_file`
+    #[default]
+    Python,
+    /// A standalone program from `path` under `[Bot Parameters]`, e.x. a Rust, Java, C#, or C++ bot
+    Executable,
+}
+
+/// The language a bot is written in, which decides what has to be installed to run it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BotLanguage {
+    Python,
+    /// JavaScript, TypeScript, or anything else that runs on Node.js
+    JavaScript,
+    /// Java, Kotlin, Scala, or anything else that runs on the JVM
+    Java,
+    /// C# or anything else that runs on .NET
+    CSharp,
+    Cpp,
+    Rust,
+    Go,
+    Scratch,
+    /// A language that couldn't be recognized, or wasn't given
+    #[default]
+    Other,
+}
+
+impl BotLanguage {
+    /// Recognizes the language from `[Details] language`, which is free text like `C# (.NET 5)` or `Rust`
+    fn from_declared(language: &str) -> Option<Self> {
+        let language = language.to_lowercase();
+        let words = language.split(|c: char| !c.is_ascii_alphanumeric()).collect::<Vec<_>>();
+        let compact = language.replace([' ', '_'], "");
+        let contains_any = |names: &[&str]| names.iter().any(|name| compact.contains(name));
+
+        // the same order as the GUI's language warnings, so `JavaScript` isn't mistaken for Java
+        if contains_any(&["python"]) {
+            Some(Self::Python)
+        } else if contains_any(&["javascript", "typescript", "coffeescript", "node"]) || words.contains(&"js") || words.contains(&"ts") {
+            Some(Self::JavaScript)
+        } else if contains_any(&["java", "kotlin", "scala"]) {
+            Some(Self::Java)
+        } else if contains_any(&["scratch"]) {
+            Some(Self::Scratch)
+        } else if contains_any(&["c#", "csharp", "dotnet", ".net"]) {
+            Some(Self::CSharp)
+        } else if contains_any(&["c++", "cpp"]) {
+            Some(Self::Cpp)
+        } else if contains_any(&["rust"]) {
+            Some(Self::Rust)
+        } else if contains_any(&["golang"]) || words.contains(&"go") {
+            Some(Self::Go)
+        } else {
+            None
+        }
+    }
+
+    /// Recognizes the language from an executable that needs a runtime, like a `.jar`
+    fn from_executable(path: &str) -> Option<Self> {
+        match Path::new(path).extension()?.to_string_lossy().to_ascii_lowercase().as_str() {
+            "jar" => Some(Self::Java),
+            "js" => Some(Self::JavaScript),
+            "py" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    /// Finds a bot's language, preferring what the config file says over the file it runs
+    fn detect(runtime: BotRuntime, declared: &str, executable_path: Option<&str>) -> Self {
+        Self::from_declared(declared)
+            .or_else(|| executable_path.and_then(Self::from_executable))
+            .unwrap_or(match runtime {
+                BotRuntime::Python => Self::Python,
+                BotRuntime::Executable => Self::Other,
+            })
+    }
+}
+
+/// Finds how a bot is ran, returning the runtime, the path to the Python file, and the path to the executable
+///
+/// A Python file is preferred, and the executable is only used if there isn't one
+fn find_runtime(conf: &Ini, config_directory: &str, config_path_str: &str) -> Result<(BotRuntime, String, Option<String>), RLBotCfgParseError> {
+    if let Some(python_path) = conf.get(BOT_CONFIG_MODULE_HEADER, PYTHON_FILE_KEY).map(|path| format!("{config_directory}/{path}")) {
+        if Path::new(&python_path).exists() {
+            return Ok((BotRuntime::Python, python_path, None));
+        }
+    }
+
+    let executable_path = conf
+        .get(BOT_CONFIG_PARAMS_HEADER, EXECUTABLE_PATH_KEY)
+        .filter(|path| !path.trim().is_empty())
+        .map(|path| format!("{config_directory}/{}", path.trim()))
+        .ok_or_else(|| RLBotCfgParseError::NoPythonFile(config_path_str.to_owned()))?;
+
+    if !Path::new(&executable_path).exists() {
+        return Err(RLBotCfgParseError::NoExecutableFile(config_path_str.to_owned(), executable_path));
+    }
+
+    Ok((BotRuntime::Executable, String::new(), Some(executable_path)))
+}
+
+/// Get every file that a config file points to, whose existence can change how the config file is parsed
+pub fn referenced_files(config_path: &Path) -> Vec<String> {
+    let (Ok(conf), Some(config_directory)) = (load_cfg_sync(config_path), config_path.parent()) else {
+        return Vec::new();
+    };
+    let config_directory = config_directory.to_string_lossy();
+
+    let mut files = [PYTHON_FILE_KEY, LOOKS_CONFIG_KEY, REQUIREMENTS_FILE_KEY, LOADOUT_GENERATOR_FILE_KEY, SCRIPT_FILE_KEY]
+        .into_iter()
+        .filter_map(|key| conf.get(BOT_CONFIG_MODULE_HEADER, key))
+        .map(|path| format!("{config_directory}/{path}"))
+        .collect::<Vec<_>>();
+
+    files.extend(
+        conf.get(BOT_CONFIG_PARAMS_HEADER, EXECUTABLE_PATH_KEY)
+            .filter(|path| !path.trim().is_empty())
+            .map(|path| format!("{config_directory}/{}", path.trim())),
+    );
+
+    files
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(default)]
 pub struct BotConfigBundle {
@@ -161,6 +230,15 @@ pub struct BotConfigBundle {
     requires_tkinter: bool,
     pub missing_python_packages: Option<Vec<String>>,
     pub python_path: String,
+    pub runtime: BotRuntime,
+    pub language: BotLanguage,
+    pub executable_path: Option<String>,
+    /// The Python file that picks the bot's loadout when a match starts, instead of the looks config
+    pub loadout_generator: Option<String>,
+    /// If the bot can be started before the match begins
+    pub supports_early_start: bool,
+    /// The highest tick rate that the bot wants to receive packets at, if it has a preference
+    pub maximum_tick_rate_preference: Option<u32>,
 }
 
 impl BotConfigBundle {
@@ -208,14 +286,7 @@ impl BotConfigBundle {
         let config_file_name = config_path.file_name().unwrap().to_string_lossy().to_string();
         let config_directory = config_path.parent().unwrap().to_string_lossy().to_string();
 
-        let python_path = conf
-            .get(BOT_CONFIG_MODULE_HEADER, PYTHON_FILE_KEY)
-            .map(|path| format!("{config_directory}/{path}"))
-            .ok_or_else(|| RLBotCfgParseError::NoPythonFile(config_path_str.clone()))?;
-
-        if !Path::new(&python_path).exists() {
-            return Err(RLBotCfgParseError::NoPythonFile(config_path_str));
-        }
+        let (runtime, python_path, executable_path) = find_runtime(conf, &config_directory, &config_path_str)?;
 
         let name = conf
             .get(BOT_CONFIG_MODULE_HEADER, NAME_KEY)
@@ -232,6 +303,19 @@ impl BotConfigBundle {
             .unwrap_or_default();
         let requirements_file = conf.get(BOT_CONFIG_MODULE_HEADER, REQUIREMENTS_FILE_KEY).map(|path| format!("{config_directory}/{path}"));
         let requires_tkinter = conf.getboolcoerce(BOT_CONFIG_MODULE_HEADER, REQUIRES_TKINTER).unwrap_or_default().unwrap_or_default();
+        let loadout_generator = conf
+            .get(BOT_CONFIG_MODULE_HEADER, LOADOUT_GENERATOR_FILE_KEY)
+            .map(|path| format!("{config_directory}/{path}"))
+            .filter(|path| Path::new(path).exists());
+        let supports_early_start = conf
+            .getboolcoerce(BOT_CONFIG_MODULE_HEADER, SUPPORTS_EARLY_START_KEY)
+            .unwrap_or_default()
+            .unwrap_or_default();
+        let maximum_tick_rate_preference = conf
+            .getuint(BOT_CONFIG_MODULE_HEADER, MAXIMUM_TICK_RATE_PREFERENCE_KEY)
+            .unwrap_or_default()
+            .and_then(|rate| u32::try_from(rate).ok())
+            .filter(|&rate| rate > 0);
 
         if !Path::new(&looks_path).exists() {
             return Err(RLBotCfgParseError::NoLooksConfig(config_path_str));
@@ -242,7 +326,9 @@ impl BotConfigBundle {
 
         let logo = None;
 
-        let info = Some(DevInfo::from_config(conf));
+        let info = DevInfo::from_config(conf);
+        let language = BotLanguage::detect(runtime, &info.language, executable_path.as_deref());
+        let info = Some(info);
 
         let runnable_type = String::from("rlbot");
         let warn = None;
@@ -268,6 +354,12 @@ impl BotConfigBundle {
             requires_tkinter,
             missing_python_packages,
             python_path,
+            runtime,
+            language,
+            executable_path,
+            loadout_generator,
+            supports_early_start,
+            maximum_tick_rate_preference,
         })
     }
 
@@ -295,15 +387,7 @@ impl BotConfigBundle {
             return Err(RLBotCfgParseError::NoLooksConfig(config_path_str));
         }
 
-        let python_path = conf
-            .get(BOT_CONFIG_MODULE_HEADER, PYTHON_FILE_KEY)
-            .map(|path| format!("{}/{path}", config_directory.display()));
-
-        let valid_path = python_path.as_ref().map_or(false, |path| Path::new(path).exists());
-
-        if !valid_path {
-            return Err(RLBotCfgParseError::NoPythonFile(config_path_str));
-        }
+        find_runtime(&conf, &config_directory.display().to_string(), &config_path_str)?;
 
         Ok((name, path))
     }
@@ -314,55 +398,31 @@ impl Runnable for BotConfigBundle {
         &self.config_file_name
     }
 
+    fn get_config_path(&self) -> &str {
+        &self.path
+    }
+
     fn get_requirements_file(&self) -> &Option<String> {
         &self.requirements_file
     }
 
     fn use_virtual_environment(&self) -> bool {
-        self.supports_standalone && self.use_virtual_environment
+        self.runtime == BotRuntime::Python && self.supports_standalone && self.use_virtual_environment
     }
 
-    fn get_missing_packages<S: AsRef<OsStr>>(&self, window: &Window, python: S) -> Vec<String> {
-        if self.use_virtual_environment() {
-            return Vec::new();
-        }
-
-        let Some(req_file) = self.get_requirements_file() else {
-            return if self.requires_tkinter && !get_command_status(python, ["-c", "import tkinter"]) {
-                vec![String::from("tkinter")]
-            } else {
-                Vec::new()
-            };
-        };
-
-        let mut args: Vec<&str> = vec!["-c", "from rlbot_smh.get_missing_packages import run; run()"];
+    fn requires_tkinter(&self) -> bool {
+        self.runtime == BotRuntime::Python && self.requires_tkinter
+    }
 
-        if self.requires_tkinter {
-            args.push("requires_tkinter");
+    fn get_missing_packages(&self, window: &Window, installed: &InstalledPackages) -> Vec<String> {
+        if self.runtime != BotRuntime::Python || self.use_virtual_environment() {
+            return Vec::new();
         }
 
-        let file = format!("requirements_file={req_file}");
-
-        args.push(&file);
-
-        let mut command = process::Command::new(python);
-
-        #[cfg(windows)]
-        {
-            // disable window creation
-            command.creation_flags(0x0800_0000);
-        };
-
-        match command.args(args).stdin(Stdio::null()).output() {
-            Ok(proc) => {
-                let output = from_utf8(proc.stdout.as_slice()).unwrap();
-                serde_json::from_str(output).unwrap_or_default()
-            }
-            Err(e) => {
-                ccprintln(window, format!("Failed to calculate missing packages: {e}"));
-                Vec::new()
-            }
-        }
+        site_packages::get_missing_packages(self.get_requirements_file().as_deref(), self.requires_tkinter, installed).unwrap_or_else(|e| {
+            ccprintln(window, format!("Failed to calculate missing packages: {e}"));
+            Vec::new()
+        })
     }
 
     fn logo(&self) -> &Option<String> {
@@ -370,7 +430,7 @@ impl Runnable for BotConfigBundle {
     }
 
     fn load_logo(&self) -> Option<String> {
-        to_base64(&self.logo_path)
+        thumbnail_url(&self.logo_path, LogoSize::Small)
     }
 
     fn is_rlbot_controlled(&self) -> bool {
@@ -386,7 +446,7 @@ impl Runnable for BotConfigBundle {
     }
 
     fn may_require_python_packages(&self) -> bool {
-        self.info.as_ref().map(|info| info.language.to_lowercase().contains("python")).unwrap_or_default()
+        self.runtime == BotRuntime::Python && self.info.as_ref().map(|info| info.language.to_lowercase().contains("python")).unwrap_or_default()
     }
 }
 
@@ -477,6 +537,10 @@ impl Runnable for ScriptConfigBundle {
         &self.config_file_name
     }
 
+    fn get_config_path(&self) -> &str {
+        &self.path
+    }
+
     fn get_requirements_file(&self) -> &Option<String> {
         &self.requirements_file
     }
@@ -485,47 +549,19 @@ impl Runnable for ScriptConfigBundle {
         self.use_virtual_environment
     }
 
-    fn get_missing_packages<S: AsRef<OsStr>>(&self, window: &Window, python: S) -> Vec<String> {
+    fn requires_tkinter(&self) -> bool {
+        self.requires_tkinter
+    }
+
+    fn get_missing_packages(&self, window: &Window, installed: &InstalledPackages) -> Vec<String> {
         if self.use_virtual_environment() {
             return Vec::new();
         }
 
-        let Some(req_file) = self.get_requirements_file() else {
-            return if self.requires_tkinter && !get_command_status(python, ["-c", "import tkinter"]) {
-                vec![String::from("tkinter")]
-            } else {
-                Vec::new()
-            };
-        };
-
-        let mut args: Vec<&str> = vec!["-c", "from rlbot_smh.get_missing_packages import run; run()"];
-
-        if self.requires_tkinter {
-            args.push("requires_tkinter");
-        }
-
-        let file = format!("requirements_file={req_file}");
-
-        args.push(&file);
-
-        let mut command = process::Command::new(python);
-
-        #[cfg(windows)]
-        {
-            // disable window creation
-            command.creation_flags(0x0800_0000);
-        };
-
-        match command.args(args).stdin(Stdio::null()).output() {
-            Ok(proc) => {
-                let output = from_utf8(proc.stdout.as_slice()).unwrap();
-                serde_json::from_str(output).unwrap_or_default()
-            }
-            Err(e) => {
-                ccprintln(window, format!("Failed to calculate missing packages: {e}"));
-                Vec::new()
-            }
-        }
+        site_packages::get_missing_packages(self.get_requirements_file().as_deref(), self.requires_tkinter, installed).unwrap_or_else(|e| {
+            ccprintln(window, format!("Failed to calculate missing packages: {e}"));
+            Vec::new()
+        })
     }
 
     fn logo(&self) -> &Option<String> {
@@ -537,7 +573,7 @@ impl Runnable for ScriptConfigBundle {
             return None;
         };
 
-        to_base64(logo_path)
+        thumbnail_url(logo_path, LogoSize::Small)
     }
 
     fn is_rlbot_controlled(&self) -> bool {
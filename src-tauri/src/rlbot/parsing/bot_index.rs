@@ -0,0 +1,300 @@
+use super::{
+    bot_config_bundle::{referenced_files, BotConfigBundle, RLBotCfgParseError, ScriptConfigBundle},
+    directory_scanner::{find_config_files, is_other_kind},
+};
+use crate::{ccprintln, get_content_folder, settings::BotFolders};
+use futures_util::{future::join_all, Future};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{create_dir_all, metadata, read_to_string, remove_file, write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+use tauri::Window;
+use thiserror::Error;
+use tokio::sync::Mutex as AsyncMutex;
+
+/// Bumped whenever the format of the index changes, so old indexes are thrown away instead of being misread
+const INDEX_VERSION: u32 = 6;
+
+static BOT_INDEX: Lazy<AsyncMutex<Option<BotIndex>>> = Lazy::new(|| AsyncMutex::new(None));
+
+#[derive(Debug, Error)]
+pub enum BotIndexError {
+    #[error("I/O error when managing the bot index: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Bot index is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A config file that has been parsed, along with what the file looked like when it was
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry<T> {
+    /// When the file was last modified, in nanoseconds since the Unix epoch
+    modified: u64,
+    size: u64,
+    /// The files that the config file points to, and if they existed when it was parsed
+    ///
+    /// Creating or deleting one of them can change the parsed item without the config file changing, e.x. adding the missing Python file to a bot
+    dependencies: Vec<(String, bool)>,
+    /// The parsed item, or None if the file is for a different kind of item
+    item: Option<T>,
+}
+
+impl<T> IndexEntry<T> {
+    fn is_current(&self, stamp: Option<(u64, u64)>) -> bool {
+        Some((self.modified, self.size)) == stamp && self.dependencies.iter().all(|(path, existed)| Path::new(path).exists() == *existed)
+    }
+}
+
+/// What happened during a single scan
+#[derive(Debug, Clone, Copy, Serialize, Default)]
+pub struct ScanStats {
+    /// Config files that were new or had changed, and so were parsed
+    pub parsed: usize,
+    /// Config files that hadn't changed, and so were taken from the index
+    pub reused: usize,
+    /// Config files that were in the index but no longer exist or aren't visible
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BotIndexStats {
+    pub path: String,
+    /// The size of the index file in bytes, or None if it hasn't been saved yet
+    pub file_size: Option<u64>,
+    pub bots: usize,
+    pub scripts: usize,
+    pub last_bot_scan: ScanStats,
+    pub last_script_scan: ScanStats,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BotIndex {
+    version: u32,
+    bots: HashMap<String, IndexEntry<BotConfigBundle>>,
+    scripts: HashMap<String, IndexEntry<ScriptConfigBundle>>,
+    #[serde(skip)]
+    last_bot_scan: ScanStats,
+    #[serde(skip)]
+    last_script_scan: ScanStats,
+}
+
+impl Default for BotIndex {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            bots: HashMap::new(),
+            scripts: HashMap::new(),
+            last_bot_scan: ScanStats::default(),
+            last_script_scan: ScanStats::default(),
+        }
+    }
+}
+
+impl BotIndex {
+    fn load() -> Result<Self, BotIndexError> {
+        let path = get_index_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let index: Self = serde_json::from_str(&read_to_string(path)?)?;
+
+        Ok(if index.version == INDEX_VERSION { index } else { Self::default() })
+    }
+
+    fn save(&self) -> Result<(), BotIndexError> {
+        let path = get_index_path();
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+
+        write(path, serde_json::to_string(self)?)?;
+
+        Ok(())
+    }
+}
+
+/// Get the path to the file where the bot index is saved
+fn get_index_path() -> PathBuf {
+    get_content_folder().join("bot_index.json")
+}
+
+/// Gets the index from memory, loading it from disk if this is the first time it's been used
+fn get_index<'a>(window: &Window, index: &'a mut Option<BotIndex>) -> &'a mut BotIndex {
+    index.get_or_insert_with(|| {
+        BotIndex::load().unwrap_or_else(|e| {
+            ccprintln!(window, "{e}");
+            BotIndex::default()
+        })
+    })
+}
+
+/// Get the modified time and size of a file, which decide if it has to be parsed again
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let metadata = metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+
+    Some((u64::try_from(modified.as_nanos()).unwrap_or(u64::MAX), metadata.len()))
+}
+
+/// Get the paths of every config file in the visible folders, skipping hidden ones, followed by the visible individual files
+pub fn visible_config_paths(bot_folders: &BotFolders) -> Vec<PathBuf> {
+    let mut paths = bot_folders
+        .folders
+        .iter()
+        .filter(|(_, props)| props.visible)
+        .flat_map(|(path, _)| find_config_files(path))
+        .filter(|path| !path.file_name().map_or(false, |name| name.to_string_lossy().starts_with('_')))
+        .collect::<Vec<_>>();
+
+    paths.extend(bot_folders.files.iter().filter(|(_, props)| props.visible).map(|(path, _)| PathBuf::from(path)));
+
+    paths
+}
+
+/// Brings the index up to date with the given config files, only parsing the ones that are new, have changed, or point to files that have been created or deleted
+///
+/// Entries for files that aren't in `paths` are removed. Files that fail to parse aren't indexed, so their error is shown again on the next scan.
+async fn update_entries<T, R, F>(window: &Window, entries: &mut HashMap<String, IndexEntry<T>>, paths: Vec<PathBuf>, mut func: F) -> (Vec<T>, ScanStats)
+where
+    T: Clone,
+    R: Future<Output = Result<T, RLBotCfgParseError>>,
+    F: FnMut(PathBuf) -> R,
+{
+    let mut seen = HashSet::new();
+    let paths = paths
+        .into_iter()
+        .map(|path| (path.to_string_lossy().to_string(), path))
+        .filter(|(key, _)| seen.insert(key.clone()))
+        .collect::<Vec<_>>();
+
+    let mut stats = ScanStats::default();
+
+    let before = entries.len();
+    entries.retain(|key, _| seen.contains(key));
+    stats.removed = before - entries.len();
+
+    let stale = paths
+        .iter()
+        .map(|(key, path)| (key, path, file_stamp(path)))
+        .filter(|(key, _, stamp)| entries.get(*key).map_or(true, |entry| !entry.is_current(*stamp)))
+        .collect::<Vec<_>>();
+
+    stats.parsed = stale.len();
+    stats.reused = paths.len() - stale.len();
+
+    let results = join_all(stale.iter().map(|(_, path, _)| func((*path).clone()))).await;
+
+    for ((key, path, stamp), result) in stale.into_iter().zip(results) {
+        let item = match result {
+            Ok(item) => Some(item),
+            Err(e) if is_other_kind(&e) => None,
+            Err(e) => {
+                ccprintln(window, e.to_string());
+                entries.remove(key);
+                continue;
+            }
+        };
+
+        // a file that can't be stat'ed is parsed again on every scan
+        match stamp {
+            Some((modified, size)) => {
+                let dependencies = referenced_files(path).into_iter().map(|file| (file.clone(), Path::new(&file).exists())).collect();
+                entries.insert(
+                    key.clone(),
+                    IndexEntry {
+                        modified,
+                        size,
+                        dependencies,
+                        item,
+                    },
+                )
+            }
+            None => entries.remove(key),
+        };
+    }
+
+    let items = paths.iter().filter_map(|(key, _)| entries.get(key)?.item.clone()).collect();
+
+    (items, stats)
+}
+
+fn save_if_changed(window: &Window, index: &BotIndex, stats: ScanStats) {
+    if stats.parsed == 0 && stats.removed == 0 {
+        return;
+    }
+
+    if let Err(e) = index.save() {
+        ccprintln!(window, "Error saving the bot index: {e}");
+    }
+}
+
+/// Get every bot in the visible bot folders and files, using the index to skip config files that haven't changed
+pub async fn scan_bots(window: &Window, bot_folders: &BotFolders) -> Vec<BotConfigBundle> {
+    let paths = visible_config_paths(bot_folders);
+
+    let mut index = BOT_INDEX.lock().await;
+    let index = get_index(window, &mut index);
+
+    let (bots, stats) = update_entries(window, &mut index.bots, paths, BotConfigBundle::minimal_from_path).await;
+    index.last_bot_scan = stats;
+    save_if_changed(window, index, stats);
+
+    bots
+}
+
+/// Get every script in the visible bot folders and files, using the index to skip config files that haven't changed
+pub async fn scan_scripts(window: &Window, bot_folders: &BotFolders) -> Vec<ScriptConfigBundle> {
+    let paths = visible_config_paths(bot_folders);
+
+    let mut index = BOT_INDEX.lock().await;
+    let index = get_index(window, &mut index);
+
+    let (scripts, stats) = update_entries(window, &mut index.scripts, paths, ScriptConfigBundle::minimal_from_path).await;
+    index.last_script_scan = stats;
+    save_if_changed(window, index, stats);
+
+    scripts
+}
+
+/// Get the paths of every bot and script config file from the last scans, without scanning the bot folders again
+pub async fn indexed_config_paths(window: &Window) -> Vec<String> {
+    let mut index = BOT_INDEX.lock().await;
+    let index = get_index(window, &mut index);
+
+    let bots = index.bots.iter().filter(|(_, entry)| entry.item.is_some()).map(|(path, _)| path.clone());
+    let scripts = index.scripts.iter().filter(|(_, entry)| entry.item.is_some()).map(|(path, _)| path.clone());
+
+    bots.chain(scripts).collect()
+}
+
+pub async fn get_stats(window: &Window) -> BotIndexStats {
+    let mut index = BOT_INDEX.lock().await;
+    let index = get_index(window, &mut index);
+    let path = get_index_path();
+
+    BotIndexStats {
+        file_size: metadata(&path).ok().map(|metadata| metadata.len()),
+        path: path.to_string_lossy().to_string(),
+        bots: index.bots.values().filter(|entry| entry.item.is_some()).count(),
+        scripts: index.scripts.values().filter(|entry| entry.item.is_some()).count(),
+        last_bot_scan: index.last_bot_scan,
+        last_script_scan: index.last_script_scan,
+    }
+}
+
+/// Forget every indexed config file, so the next scan parses everything again
+pub async fn clear() -> Result<(), BotIndexError> {
+    *BOT_INDEX.lock().await = Some(BotIndex::default());
+
+    let path = get_index_path();
+    if path.exists() {
+        remove_file(path)?;
+    }
+
+    Ok(())
+}
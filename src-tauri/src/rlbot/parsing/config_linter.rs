@@ -0,0 +1,623 @@
+use super::bot_config_bundle::{
+    BOT_CONFIG_DETAILS_HEADER, BOT_CONFIG_MODULE_HEADER, BOT_CONFIG_PARAMS_HEADER, EXECUTABLE_PATH_KEY, LOADOUT_GENERATOR_FILE_KEY, LOGO_FILE_KEY, LOOKS_CONFIG_KEY,
+    MAXIMUM_TICK_RATE_PREFERENCE_KEY, NAME_KEY, PYTHON_FILE_KEY, REQUIREMENTS_FILE_KEY, REQUIRES_TKINTER, SUPPORTS_EARLY_START_KEY, SUPPORTS_STANDALONE,
+    USE_VIRTUAL_ENVIRONMENT_KEY,
+};
+use crate::rlbot::agents::base_script::SCRIPT_FILE_KEY;
+use image::io::Reader as ImageReader;
+use serde::Serialize;
+use std::{collections::HashMap, fs, path::Path};
+use thiserror::Error;
+
+/// Every key that RLBot reads from the `[Locations]` section
+const LOCATIONS_KEYS: [&str; 12] = [
+    NAME_KEY,
+    LOOKS_CONFIG_KEY,
+    PYTHON_FILE_KEY,
+    SCRIPT_FILE_KEY,
+    REQUIREMENTS_FILE_KEY,
+    LOGO_FILE_KEY,
+    REQUIRES_TKINTER,
+    USE_VIRTUAL_ENVIRONMENT_KEY,
+    SUPPORTS_STANDALONE,
+    SUPPORTS_EARLY_START_KEY,
+    LOADOUT_GENERATOR_FILE_KEY,
+    MAXIMUM_TICK_RATE_PREFERENCE_KEY,
+];
+
+/// Every key that the GUI reads from the `[Details]` section
+const DETAILS_KEYS: [&str; 6] = ["developer", "description", "fun_fact", "github", "language", "tags"];
+
+/// The tags that the bot categories in the GUI filter on
+const KNOWN_TAGS: [&str; 11] = [
+    "1v1",
+    "teamplay",
+    "goalie",
+    "hoops",
+    "dropshot",
+    "snow-day",
+    "rumble",
+    "spike-rush",
+    "heatseeker",
+    "memebot",
+    "human",
+];
+
+#[derive(Debug, Error)]
+pub enum LintError {
+    #[error("Couldn't read {0}: {1}")]
+    Io(String, std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The config can't be loaded, or the bot won't run
+    Error,
+    /// The config loads, but something is probably not what the author intended
+    Warning,
+    /// A suggestion that doesn't affect how the bot runs
+    Info,
+}
+
+/// A single problem with a config file
+#[derive(Debug, Clone, Serialize)]
+pub struct LintDiagnostic {
+    pub severity: Severity,
+    pub section: Option<String>,
+    pub key: Option<String>,
+    /// The line the problem is on, starting at 1
+    pub line: Option<usize>,
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintReport {
+    pub path: String,
+    /// If the file has a `[Locations]` section, which is what makes it a bot or script config instead of e.x. a looks config
+    pub is_runnable_config: bool,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+/// A key and value from a config file, along with where it was found
+struct RawEntry {
+    section: String,
+    key: String,
+    value: String,
+    line: usize,
+}
+
+/// A config file that has been read line-by-line, so problems can be tied to the line they're on
+#[derive(Default)]
+struct RawCfg {
+    /// Every section header, lowercased, with it's line
+    sections: Vec<(String, usize)>,
+    entries: Vec<RawEntry>,
+}
+
+impl RawCfg {
+    fn parse(text: &str, diagnostics: &mut Vec<LintDiagnostic>) -> Self {
+        let mut cfg = Self::default();
+        let mut section = String::new();
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                section = name.trim().to_lowercase();
+                cfg.sections.push((section.clone(), index + 1));
+                continue;
+            }
+
+            // indented lines continue the value of the key above them
+            if raw_line.starts_with(char::is_whitespace) {
+                if let Some(entry) = cfg.entries.last_mut().filter(|entry| entry.section == section) {
+                    entry.value.push('\n');
+                    entry.value.push_str(line);
+                    continue;
+                }
+            }
+
+            let Some(split) = line.find(|c| c == '=' || c == ':') else {
+                diagnostics.push(LintDiagnostic {
+                    severity: Severity::Warning,
+                    section: Some(section.clone()).filter(|section| !section.is_empty()),
+                    key: None,
+                    line: Some(index + 1),
+                    message: format!("'{line}' isn't a key = value pair"),
+                    fix: Some("Add a value with `key = value`, or comment the line out with ';'".to_owned()),
+                });
+                continue;
+            };
+
+            cfg.entries.push(RawEntry {
+                section: section.clone(),
+                key: line[..split].trim().to_lowercase(),
+                value: line[split + 1..].trim().to_owned(),
+                line: index + 1,
+            });
+        }
+
+        cfg
+    }
+
+    fn has_section(&self, section: &str) -> bool {
+        let section = section.to_lowercase();
+        self.sections.iter().any(|(name, _)| *name == section)
+    }
+
+    /// Get an entry, where the last one wins if the key is defined more than once
+    fn get(&self, section: &str, key: &str) -> Option<&RawEntry> {
+        let section = section.to_lowercase();
+        self.entries.iter().rev().find(|entry| entry.section == section && entry.key == key)
+    }
+
+    fn section_line(&self, section: &str) -> Option<usize> {
+        let section = section.to_lowercase();
+        self.sections.iter().find(|(name, _)| *name == section).map(|(_, line)| *line)
+    }
+}
+
+fn diagnostic(severity: Severity, section: &str, entry: Option<&RawEntry>, message: String, fix: String) -> LintDiagnostic {
+    LintDiagnostic {
+        severity,
+        section: Some(section.to_owned()),
+        key: entry.map(|entry| entry.key.clone()),
+        line: entry.map(|entry| entry.line),
+        message,
+        fix: Some(fix),
+    }
+}
+
+/// The number of single-character edits it takes to turn one string into another
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+fn check_unknown_keys(cfg: &RawCfg, section: &str, known_keys: &[&str], diagnostics: &mut Vec<LintDiagnostic>) {
+    let lowercase_section = section.to_lowercase();
+
+    for entry in cfg.entries.iter().filter(|entry| entry.section == lowercase_section) {
+        if known_keys.contains(&entry.key.as_str()) {
+            continue;
+        }
+
+        let closest = known_keys
+            .iter()
+            .map(|key| (edit_distance(&entry.key, key), key))
+            .min()
+            .filter(|(distance, _)| *distance <= 2);
+        let fix = match closest {
+            Some((_, key)) => format!("Did you mean `{key}`?"),
+            None => format!("Remove the key, [{section}] only supports: {}", known_keys.join(", ")),
+        };
+
+        diagnostics.push(diagnostic(Severity::Warning, section, Some(entry), format!("Unknown key '{}' is ignored", entry.key), fix));
+    }
+}
+
+fn check_duplicates(cfg: &RawCfg, diagnostics: &mut Vec<LintDiagnostic>) {
+    let mut sections = HashMap::new();
+    for (section, line) in &cfg.sections {
+        if let Some(first_line) = sections.insert(section, *line) {
+            diagnostics.push(LintDiagnostic {
+                severity: Severity::Warning,
+                section: Some(section.clone()),
+                key: None,
+                line: Some(*line),
+                message: format!("Section [{section}] is defined again, it was first defined on line {first_line}"),
+                fix: Some("Merge the two sections into one".to_owned()),
+            });
+        }
+    }
+
+    let mut keys = HashMap::new();
+    for entry in &cfg.entries {
+        if let Some(first_line) = keys.insert((&entry.section, &entry.key), entry.line) {
+            diagnostics.push(diagnostic(
+                Severity::Warning,
+                &entry.section,
+                Some(entry),
+                format!("Key '{}' is defined again, it was first defined on line {first_line}", entry.key),
+                "Remove one of the definitions, only the last one is used".to_owned(),
+            ));
+        }
+    }
+}
+
+/// Checks that a key is present and that the file it points to exists, returning the full path to the file
+fn check_file(cfg: &RawCfg, config_directory: &Path, key: &str, severity: Severity, missing_fix: &str, diagnostics: &mut Vec<LintDiagnostic>) -> Option<String> {
+    let Some(entry) = cfg.get(BOT_CONFIG_MODULE_HEADER, key) else {
+        diagnostics.push(diagnostic(
+            severity,
+            BOT_CONFIG_MODULE_HEADER,
+            None,
+            format!("No {key} in [{BOT_CONFIG_MODULE_HEADER}]"),
+            missing_fix.to_owned(),
+        ));
+        return None;
+    };
+
+    let path = config_directory.join(&entry.value);
+    if !path.exists() {
+        diagnostics.push(diagnostic(
+            severity,
+            BOT_CONFIG_MODULE_HEADER,
+            Some(entry),
+            format!("{key} {} doesn't exist", path.display()),
+            format!("Fix the path in {key}, it's relative to the folder of the config file"),
+        ));
+        return None;
+    }
+
+    Some(path.to_string_lossy().to_string())
+}
+
+fn check_runtime(cfg: &RawCfg, config_directory: &Path, diagnostics: &mut Vec<LintDiagnostic>) {
+    if cfg.get(BOT_CONFIG_MODULE_HEADER, SCRIPT_FILE_KEY).is_some() {
+        check_file(cfg, config_directory, SCRIPT_FILE_KEY, Severity::Error, "", diagnostics);
+        return;
+    }
+
+    let executable = cfg.get(BOT_CONFIG_PARAMS_HEADER, EXECUTABLE_PATH_KEY).filter(|entry| !entry.value.is_empty());
+
+    if let Some(executable) = executable {
+        // the executable is only used when there isn't a Python file
+        let python_file_exists = cfg
+            .get(BOT_CONFIG_MODULE_HEADER, PYTHON_FILE_KEY)
+            .map_or(false, |entry| config_directory.join(&entry.value).exists());
+
+        if !python_file_exists && !config_directory.join(&executable.value).exists() {
+            diagnostics.push(diagnostic(
+                Severity::Error,
+                BOT_CONFIG_PARAMS_HEADER,
+                Some(executable),
+                format!("Executable {} doesn't exist", config_directory.join(&executable.value).display()),
+                "Build the bot, or fix the path, which is relative to the folder of the config file".to_owned(),
+            ));
+        }
+
+        return;
+    }
+
+    check_file(
+        cfg,
+        config_directory,
+        PYTHON_FILE_KEY,
+        Severity::Error,
+        &format!("Add `{PYTHON_FILE_KEY} = bot.py` to [{BOT_CONFIG_MODULE_HEADER}], or `{EXECUTABLE_PATH_KEY} = ...` to [{BOT_CONFIG_PARAMS_HEADER}]"),
+        diagnostics,
+    );
+}
+
+fn check_logo(cfg: &RawCfg, config_directory: &Path, diagnostics: &mut Vec<LintDiagnostic>) {
+    let entry = cfg.get(BOT_CONFIG_MODULE_HEADER, LOGO_FILE_KEY);
+    let path = config_directory.join(entry.map_or("logo.png", |entry| entry.value.as_str()));
+
+    if !path.exists() {
+        // a missing logo.png is fine when it wasn't asked for
+        if entry.is_some() {
+            diagnostics.push(diagnostic(
+                Severity::Warning,
+                BOT_CONFIG_MODULE_HEADER,
+                entry,
+                format!("Logo {} doesn't exist", path.display()),
+                format!("Fix the path in {LOGO_FILE_KEY}, or remove it"),
+            ));
+        }
+        return;
+    }
+
+    // only the header is read, which is all it takes to know if the GUI can show the logo
+    let format = ImageReader::open(&path).and_then(ImageReader::with_guessed_format).map(|reader| reader.format());
+    if !matches!(format, Ok(Some(_))) {
+        diagnostics.push(diagnostic(
+            Severity::Warning,
+            BOT_CONFIG_MODULE_HEADER,
+            entry,
+            format!("Logo {} isn't a recognised image", path.display()),
+            "Save the logo as a PNG".to_owned(),
+        ));
+    }
+}
+
+fn check_tags(cfg: &RawCfg, diagnostics: &mut Vec<LintDiagnostic>) {
+    let Some(entry) = cfg.get(BOT_CONFIG_DETAILS_HEADER, "tags") else {
+        return;
+    };
+
+    if entry.value.is_empty() {
+        return;
+    }
+
+    let tags = entry.value.split(", ").collect::<Vec<_>>();
+
+    if tags.iter().any(|tag| tag.is_empty() || tag.contains(',') || tag.trim() != *tag) {
+        let fixed = entry.value.split(',').map(str::trim).filter(|tag| !tag.is_empty()).collect::<Vec<_>>().join(", ");
+        diagnostics.push(diagnostic(
+            Severity::Warning,
+            BOT_CONFIG_DETAILS_HEADER,
+            Some(entry),
+            format!("Tags '{}' aren't separated by ', '", entry.value),
+            format!("Change it to `tags = {fixed}`"),
+        ));
+        return;
+    }
+
+    for tag in tags.into_iter().filter(|tag| !KNOWN_TAGS.contains(tag)) {
+        diagnostics.push(diagnostic(
+            Severity::Info,
+            BOT_CONFIG_DETAILS_HEADER,
+            Some(entry),
+            format!("Tag '{tag}' isn't used by any bot category"),
+            format!("The tags with categories are: {}", KNOWN_TAGS.join(", ")),
+        ));
+    }
+}
+
+/// Finds every problem in a bot or script config file
+///
+/// # Arguments
+///
+/// * `path` - The path to the config file
+pub fn lint_config(path: &Path) -> Result<LintReport, LintError> {
+    let bytes = fs::read(path).map_err(|e| LintError::Io(path.to_string_lossy().to_string(), e))?;
+
+    Ok(lint_bytes(path, bytes))
+}
+
+/// Finds every problem in the contents of a config file, with files that it refers to being looked up relative to `path`
+fn lint_bytes(path: &Path, bytes: Vec<u8>) -> LintReport {
+    let path_str = path.to_string_lossy().to_string();
+    let mut diagnostics = Vec::new();
+
+    let text = match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => {
+            let valid = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+            diagnostics.push(LintDiagnostic {
+                severity: Severity::Error,
+                section: None,
+                key: None,
+                line: Some(valid.iter().filter(|byte| **byte == b'\n').count() + 1),
+                message: "The file isn't valid UTF-8, so it can't be loaded".to_owned(),
+                fix: Some("Save the file with UTF-8 encoding".to_owned()),
+            });
+            String::from_utf8_lossy(e.as_bytes()).to_string()
+        }
+    };
+
+    let cfg = RawCfg::parse(&text, &mut diagnostics);
+    check_duplicates(&cfg, &mut diagnostics);
+
+    let is_runnable_config = cfg.has_section(BOT_CONFIG_MODULE_HEADER);
+    if !is_runnable_config {
+        diagnostics.push(LintDiagnostic {
+            severity: Severity::Error,
+            section: None,
+            key: None,
+            line: None,
+            message: format!("No [{BOT_CONFIG_MODULE_HEADER}] section"),
+            fix: Some(format!(
+                "Add a [{BOT_CONFIG_MODULE_HEADER}] section with at least `{NAME_KEY}`, `{LOOKS_CONFIG_KEY}`, and `{PYTHON_FILE_KEY}`"
+            )),
+        });
+
+        return LintReport {
+            path: path_str,
+            is_runnable_config,
+            diagnostics,
+        };
+    }
+
+    let config_directory = path.parent().unwrap_or_else(|| Path::new(""));
+
+    match cfg.get(BOT_CONFIG_MODULE_HEADER, NAME_KEY) {
+        Some(entry) if !entry.value.is_empty() => {}
+        entry => diagnostics.push(LintDiagnostic {
+            line: entry.map(|entry| entry.line).or_else(|| cfg.section_line(BOT_CONFIG_MODULE_HEADER)),
+            ..diagnostic(
+                Severity::Error,
+                BOT_CONFIG_MODULE_HEADER,
+                entry,
+                "No name".to_owned(),
+                format!("Add `{NAME_KEY} = My Bot` to [{BOT_CONFIG_MODULE_HEADER}]"),
+            )
+        }),
+    }
+
+    let is_script = cfg.get(BOT_CONFIG_MODULE_HEADER, SCRIPT_FILE_KEY).is_some();
+    if !is_script {
+        check_file(
+            &cfg,
+            config_directory,
+            LOOKS_CONFIG_KEY,
+            Severity::Error,
+            &format!("Add `{LOOKS_CONFIG_KEY} = appearance.cfg` to [{BOT_CONFIG_MODULE_HEADER}]"),
+            &mut diagnostics,
+        );
+    }
+
+    check_runtime(&cfg, config_directory, &mut diagnostics);
+
+    if let Some(entry) = cfg.get(BOT_CONFIG_MODULE_HEADER, REQUIREMENTS_FILE_KEY) {
+        if !config_directory.join(&entry.value).exists() {
+            diagnostics.push(diagnostic(
+                Severity::Warning,
+                BOT_CONFIG_MODULE_HEADER,
+                Some(entry),
+                format!("Requirements file {} doesn't exist", config_directory.join(&entry.value).display()),
+                format!("Fix the path in {REQUIREMENTS_FILE_KEY}, or remove it if the bot doesn't need any packages"),
+            ));
+        }
+    }
+
+    if let Some(entry) = cfg.get(BOT_CONFIG_MODULE_HEADER, LOADOUT_GENERATOR_FILE_KEY) {
+        if !config_directory.join(&entry.value).exists() {
+            diagnostics.push(diagnostic(
+                Severity::Warning,
+                BOT_CONFIG_MODULE_HEADER,
+                Some(entry),
+                format!(
+                    "Loadout generator {} doesn't exist, so the looks config will be used instead",
+                    config_directory.join(&entry.value).display()
+                ),
+                format!("Fix the path in {LOADOUT_GENERATOR_FILE_KEY}, or remove it"),
+            ));
+        }
+    }
+
+    check_logo(&cfg, config_directory, &mut diagnostics);
+    check_unknown_keys(&cfg, BOT_CONFIG_MODULE_HEADER, &LOCATIONS_KEYS, &mut diagnostics);
+    check_unknown_keys(&cfg, BOT_CONFIG_DETAILS_HEADER, &DETAILS_KEYS, &mut diagnostics);
+    check_tags(&cfg, &mut diagnostics);
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line.unwrap_or_default());
+
+    LintReport {
+        path: path_str,
+        is_runnable_config,
+        diagnostics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(text: &str) -> LintReport {
+        lint_bytes(Path::new("bot/bot.cfg"), text.as_bytes().to_vec())
+    }
+
+    /// Finds the only diagnostic whose message starts with `message`
+    fn find<'a>(report: &'a LintReport, message: &str) -> &'a LintDiagnostic {
+        let mut found = report.diagnostics.iter().filter(|diagnostic| diagnostic.message.starts_with(message));
+        let diagnostic = found
+            .next()
+            .unwrap_or_else(|| panic!("No diagnostic starting with '{message}' in {:?}", report.diagnostics));
+        assert!(found.next().is_none(), "More than one diagnostic starts with '{message}'");
+        diagnostic
+    }
+
+    fn assert_diagnostic(diagnostic: &LintDiagnostic, severity: Severity, section: Option<&str>, key: Option<&str>, line: Option<usize>) {
+        assert_eq!(diagnostic.severity, severity);
+        assert_eq!(diagnostic.section.as_deref(), section);
+        assert_eq!(diagnostic.key.as_deref(), key);
+        assert_eq!(diagnostic.line, line);
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let report = lint("[Locations]\nname = Bot\npython_fil = bot.py\nfavourite_colour = blue\n\n[Details]\ndeveloper = Me\ndescriptoin = A bot\n");
+
+        let typo = find(&report, "Unknown key 'python_fil'");
+        assert_diagnostic(typo, Severity::Warning, Some("Locations"), Some("python_fil"), Some(3));
+        assert_eq!(typo.fix.as_deref(), Some("Did you mean `python_file`?"));
+
+        let unknown = find(&report, "Unknown key 'favourite_colour'");
+        assert_diagnostic(unknown, Severity::Warning, Some("Locations"), Some("favourite_colour"), Some(4));
+        assert!(unknown.fix.as_ref().unwrap().starts_with("Remove the key, [Locations] only supports: "));
+
+        let details_typo = find(&report, "Unknown key 'descriptoin'");
+        assert_diagnostic(details_typo, Severity::Warning, Some("Details"), Some("descriptoin"), Some(8));
+        assert_eq!(details_typo.fix.as_deref(), Some("Did you mean `description`?"));
+
+        assert!(report.diagnostics.iter().all(|diagnostic| diagnostic.key.as_deref() != Some("developer")));
+    }
+
+    #[test]
+    fn duplicates() {
+        let report = lint("[Locations]\nname = Bot\nname = Other Bot\n\n[Details]\ndeveloper = Me\n\n[Locations]\nlooks_config = appearance.cfg\n");
+
+        let section = find(&report, "Section [locations] is defined again");
+        assert_diagnostic(section, Severity::Warning, Some("locations"), None, Some(8));
+        assert!(section.message.ends_with("first defined on line 1"));
+
+        let key = find(&report, "Key 'name' is defined again");
+        assert_diagnostic(key, Severity::Warning, Some("locations"), Some("name"), Some(3));
+        assert!(key.message.ends_with("first defined on line 2"));
+
+        // keys with the same name in different sections aren't duplicates
+        let report = lint("[Locations]\nname = Bot\n\n[Details]\nname = Bot\n");
+        assert!(report.diagnostics.iter().all(|diagnostic| !diagnostic.message.contains("defined again")));
+    }
+
+    #[test]
+    fn tags() {
+        let report = lint("[Locations]\nname = Bot\n\n[Details]\ntags = 1v1,teamplay , goalie\n");
+        let separators = find(&report, "Tags '1v1,teamplay , goalie' aren't separated by ', '");
+        assert_diagnostic(separators, Severity::Warning, Some("Details"), Some("tags"), Some(5));
+        assert_eq!(separators.fix.as_deref(), Some("Change it to `tags = 1v1, teamplay, goalie`"));
+
+        let report = lint("[Locations]\nname = Bot\n\n[Details]\ntags = 1v1, speedflip, goalie\n");
+        assert_diagnostic(find(&report, "Tag 'speedflip'"), Severity::Info, Some("Details"), Some("tags"), Some(5));
+        assert!(report.diagnostics.iter().all(|diagnostic| !diagnostic.message.starts_with("Tag '1v1'")));
+
+        let report = lint("[Locations]\nname = Bot\n\n[Details]\ntags = 1v1, goalie\n");
+        assert!(report.diagnostics.iter().all(|diagnostic| !diagnostic.message.starts_with("Tag")));
+    }
+
+    #[test]
+    fn non_utf8() {
+        let mut bytes = b"[Locations]\nname = Bot\ndeveloper = ".to_vec();
+        bytes.extend([0xff, 0xfe]);
+        bytes.extend(b"\n");
+
+        let report = lint_bytes(Path::new("bot/bot.cfg"), bytes);
+        assert_diagnostic(find(&report, "The file isn't valid UTF-8"), Severity::Error, None, None, Some(3));
+
+        // the rest of the file is still linted
+        assert!(report.is_runnable_config);
+        find(&report, "Unknown key 'developer'");
+    }
+
+    #[test]
+    fn line_numbers() {
+        let report = lint("; a comment\n# another comment\n\n[Locations]\nname = Bot\n\nnot a pair\nlooks_confg = appearance.cfg\n");
+
+        assert_diagnostic(find(&report, "'not a pair' isn't a key = value pair"), Severity::Warning, Some("locations"), None, Some(7));
+        assert_diagnostic(
+            find(&report, "Unknown key 'looks_confg'"),
+            Severity::Warning,
+            Some("Locations"),
+            Some("looks_confg"),
+            Some(8),
+        );
+
+        let lines = report.diagnostics.iter().map(|diagnostic| diagnostic.line.unwrap_or_default()).collect::<Vec<_>>();
+        assert!(lines.windows(2).all(|pair| pair[0] <= pair[1]), "Diagnostics aren't sorted by line: {lines:?}");
+    }
+
+    #[test]
+    fn missing_files() {
+        let report = lint("[Locations]\nname = Bot\nlooks_config = appearance.cfg\npython_file = bot.py\nlogo_file = logo.png\n");
+
+        assert_diagnostic(find(&report, "looks_config "), Severity::Error, Some("Locations"), Some("looks_config"), Some(3));
+        assert_diagnostic(find(&report, "python_file "), Severity::Error, Some("Locations"), Some("python_file"), Some(4));
+        assert_diagnostic(find(&report, "Logo "), Severity::Warning, Some("Locations"), Some("logo_file"), Some(5));
+    }
+
+    #[test]
+    fn not_a_bot_config() {
+        let report = lint("[Bot Loadout]\nteam_color_id = 1\n");
+
+        assert!(!report.is_runnable_config);
+        assert_diagnostic(find(&report, "No [Locations] section"), Severity::Error, None, None, None);
+    }
+}
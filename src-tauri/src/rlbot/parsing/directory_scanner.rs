@@ -1,4 +1,4 @@
-use super::bot_config_bundle::{BotConfigBundle, RLBotCfgParseError, ScriptConfigBundle};
+use super::bot_config_bundle::{BotConfigBundle, RLBotCfgParseError};
 use crate::ccprintln;
 use futures_util::{future::join_all, Future};
 use glob::glob;
@@ -12,11 +12,14 @@ pub async fn scan_directory_for_bot_configs(window: &Window, root_dir: &str) ->
     scan_directory_for_item(window, root_dir, BotConfigBundle::minimal_from_path).await
 }
 
-/// Scan `root_dir` for SCRIPTS (no bots) and parse the configuration files, returning unique `ScriptConfigBundle`s
-///
-/// Does not load logos or missing python packages, but the paths to the logo file and requirements.txt WILL be loaded, if they exists
-pub async fn scan_directory_for_script_configs(window: &Window, root_dir: &str) -> Vec<ScriptConfigBundle> {
-    scan_directory_for_item(window, root_dir, ScriptConfigBundle::minimal_from_path).await
+/// Find the path of every config file in `root_dir`, including subfolders
+pub fn find_config_files(root_dir: &str) -> Vec<PathBuf> {
+    glob(&format!("{root_dir}/**/*.cfg")).unwrap().flatten().collect()
+}
+
+/// Checks if an error only means that the config file is for a different kind of item, e.x. a script when looking for bots
+pub fn is_other_kind(err: &RLBotCfgParseError) -> bool {
+    matches!(err, RLBotCfgParseError::NoPythonFile(_) | RLBotCfgParseError::NoScriptFile(_))
 }
 
 /// Scan `root_dir` for and run func on each item found, filtering items that returned errors.
@@ -28,22 +31,16 @@ where
     R: Future<Output = Result<T, RLBotCfgParseError>>,
     F: FnMut(PathBuf) -> R,
 {
-    join_all(glob(&format!("{root_dir}/**/*.cfg")).unwrap().flatten().map(func))
+    join_all(find_config_files(root_dir).into_iter().map(func))
         .await
         .into_iter()
         .filter_map(|bundle| match bundle {
             Ok(bundle) => Some(bundle),
             Err(err) => {
-                if let RLBotCfgParseError::NoPythonFile(_) = err {
-                    return None;
+                if !is_other_kind(&err) {
+                    ccprintln(window, err.to_string());
                 }
 
-                if let RLBotCfgParseError::NoScriptFile(_) = err {
-                    return None;
-                }
-
-                ccprintln(window, err.to_string());
-
                 None
             }
         })
@@ -0,0 +1,395 @@
+use super::agent_config_parser::{BotLooksConfig, BotTeamLooksConfig};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// The same file that the appearance editor loads, so both always agree on what items exist
+const ITEMS_CSV: &str = include_str!("../../../../assets/csv/items.csv");
+
+/// The same colors that the appearance editor's color pickers show
+const COLORS_JSON: &str = include_str!("../../../../assets/json/colors.json");
+
+pub static ITEM_CATALOG: Lazy<ItemCatalog> = Lazy::new(|| ItemCatalog::from_csv(ITEMS_CSV));
+
+pub static COLOR_PALETTES: Lazy<ColorPalettes> = Lazy::new(|| serde_json::from_str(COLORS_JSON).unwrap_or_default());
+
+/// The RGB values of the colors that `team_color_id` and `custom_color_id` pick from
+#[derive(Debug, Default, Deserialize)]
+pub struct ColorPalettes {
+    /// Primary colors for the blue team
+    pub blue: Vec<[u8; 3]>,
+    /// Primary colors for the orange team
+    pub orange: Vec<[u8; 3]>,
+    /// Accent colors, which are the same for both teams
+    pub secondary: Vec<[u8; 3]>,
+}
+
+/// The loadout slots that an item can go in, named the same as in `items.csv`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ItemSlot {
+    Body,
+    Skin,
+    Wheels,
+    Boost,
+    Antenna,
+    Hat,
+    PaintFinish,
+    EngineAudio,
+    SupersonicTrail,
+    GoalExplosion,
+}
+
+impl ItemSlot {
+    fn from_csv(slot: &str) -> Option<Self> {
+        Some(match slot {
+            "Body" => Self::Body,
+            "Skin" => Self::Skin,
+            "Wheels" => Self::Wheels,
+            "Boost" => Self::Boost,
+            "Antenna" => Self::Antenna,
+            "Hat" => Self::Hat,
+            "PaintFinish" => Self::PaintFinish,
+            "EngineAudio" => Self::EngineAudio,
+            "SupersonicTrail" => Self::SupersonicTrail,
+            "GoalExplosion" => Self::GoalExplosion,
+            _ => return None,
+        })
+    }
+
+    /// What the slot is called in the appearance editor
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Body => "car",
+            Self::Skin => "decal",
+            Self::Wheels => "wheels",
+            Self::Boost => "boost",
+            Self::Antenna => "antenna",
+            Self::Hat => "hat",
+            Self::PaintFinish => "paint finish",
+            Self::EngineAudio => "engine audio",
+            Self::SupersonicTrail => "trail",
+            Self::GoalExplosion => "goal explosion",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Item {
+    pub id: u32,
+    pub slot: ItemSlot,
+    /// The name of the item in the game files, like `Product_TA ProductsDB.Products.Antenna_8Ball`
+    pub product: String,
+    pub name: String,
+}
+
+#[derive(Debug, Error)]
+pub enum LoadoutError {
+    #[error("{team} {slot} \"{id}\" is not a number")]
+    NotANumber { team: &'static str, slot: &'static str, id: String },
+    #[error("{team} {slot} {id} is not a known item")]
+    UnknownItem { team: &'static str, slot: &'static str, id: u32 },
+    #[error("{team} {slot} {id} is the {kind} item \"{name}\", not a {slot} item")]
+    WrongSlot {
+        team: &'static str,
+        slot: &'static str,
+        id: u32,
+        kind: &'static str,
+        name: String,
+    },
+}
+
+/// Every loadout item that can be picked, from `items.csv`
+#[derive(Debug, Default)]
+pub struct ItemCatalog {
+    items: Vec<Item>,
+    by_id: HashMap<u32, usize>,
+}
+
+impl ItemCatalog {
+    /// Parses lines in the format `id,Slot,ProductPath,Name`, skipping items that don't go in a loadout slot
+    fn from_csv(csv: &str) -> Self {
+        let mut catalog = Self::default();
+
+        for line in csv.lines() {
+            // names can contain commas, but the other columns can't
+            let mut columns = line.trim_end().splitn(4, ',');
+            let (Some(id), Some(slot), Some(product), Some(name)) = (columns.next(), columns.next(), columns.next(), columns.next()) else {
+                continue;
+            };
+
+            let (Ok(id), Some(slot)) = (id.parse(), ItemSlot::from_csv(slot)) else {
+                continue;
+            };
+
+            catalog.by_id.insert(id, catalog.items.len());
+            catalog.items.push(Item {
+                id,
+                slot,
+                product: product.to_owned(),
+                name: name.to_owned(),
+            });
+        }
+
+        catalog
+    }
+
+    pub fn by_id(&self, id: u32) -> Option<&Item> {
+        self.by_id.get(&id).map(|&index| &self.items[index])
+    }
+
+    /// Get every item in a slot with exactly this name, ignoring case, since some names are shared by multiple items
+    pub fn by_name(&self, slot: ItemSlot, name: &str) -> Vec<&Item> {
+        self.in_slot(slot).filter(|item| item.name.eq_ignore_ascii_case(name.trim())).collect()
+    }
+
+    pub fn in_slot(&self, slot: ItemSlot) -> impl Iterator<Item = &Item> {
+        self.items.iter().filter(move |item| item.slot == slot)
+    }
+
+    /// Get the items whose name contains every word in the query, optionally only in one slot
+    ///
+    /// Items whose name is exactly the query come first
+    pub fn search(&self, query: &str, slot: Option<ItemSlot>) -> Vec<&Item> {
+        let query = query.trim().to_lowercase();
+        let words = query.split_whitespace().collect::<Vec<_>>();
+
+        let mut items = self
+            .items
+            .iter()
+            .filter(|item| slot.map_or(true, |slot| item.slot == slot))
+            .filter(|item| {
+                let name = item.name.to_lowercase();
+                words.iter().all(|word| name.contains(word))
+            })
+            .collect::<Vec<_>>();
+
+        items.sort_by_key(|item| item.name.to_lowercase() != query);
+
+        items
+    }
+
+    /// Checks that an item ID in a looks config exists and goes in the given slot
+    ///
+    /// Empty IDs and 0 are allowed, as they mean the slot is left empty
+    fn check(&self, team: &'static str, slot: ItemSlot, id: &str) -> Result<(), LoadoutError> {
+        let id = id.trim();
+        if id.is_empty() {
+            return Ok(());
+        }
+
+        let slot_name = slot.display_name();
+        let id = id.parse().map_err(|_| LoadoutError::NotANumber {
+            team,
+            slot: slot_name,
+            id: id.to_owned(),
+        })?;
+
+        if id == 0 {
+            return Ok(());
+        }
+
+        let item = self.by_id(id).ok_or(LoadoutError::UnknownItem { team, slot: slot_name, id })?;
+
+        if item.slot != slot {
+            return Err(LoadoutError::WrongSlot {
+                team,
+                slot: slot_name,
+                id,
+                kind: item.slot.display_name(),
+                name: item.name.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn validate_team(&self, team: &'static str, looks: &BotTeamLooksConfig) -> Result<(), LoadoutError> {
+        let ids = [
+            (ItemSlot::Body, &looks.car_id),
+            (ItemSlot::Skin, &looks.decal_id),
+            (ItemSlot::Wheels, &looks.wheels_id),
+            (ItemSlot::Boost, &looks.boost_id),
+            (ItemSlot::Antenna, &looks.antenna_id),
+            (ItemSlot::Hat, &looks.hat_id),
+            (ItemSlot::PaintFinish, &looks.paint_finish_id),
+            (ItemSlot::PaintFinish, &looks.custom_finish_id),
+            (ItemSlot::EngineAudio, &looks.engine_audio_id),
+            (ItemSlot::SupersonicTrail, &looks.trails_id),
+            (ItemSlot::GoalExplosion, &looks.goal_explosion_id),
+        ];
+
+        ids.into_iter().try_for_each(|(slot, id)| self.check(team, slot, id))
+    }
+
+    /// Checks that every item ID in both teams' loadouts exists and is in the right slot
+    pub fn validate(&self, looks: &BotLooksConfig) -> Result<(), LoadoutError> {
+        self.validate_team("Blue", &looks.blue)?;
+        self.validate_team("Orange", &looks.orange)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(items: &[&Item]) -> Vec<u32> {
+        items.iter().map(|item| item.id).collect()
+    }
+
+    fn looks(blue: BotTeamLooksConfig, orange: BotTeamLooksConfig) -> BotLooksConfig {
+        BotLooksConfig { blue, orange }
+    }
+
+    #[test]
+    fn lookups() {
+        let octane = ITEM_CATALOG.by_id(23).unwrap();
+        assert_eq!(octane.slot, ItemSlot::Body);
+        assert_eq!(octane.name, "Octane");
+        assert_eq!(octane.product, "Product_TA ProductsDB.Products.Body_Octane");
+
+        // items that don't go in a loadout slot, like banners, are skipped
+        assert!(ITEM_CATALOG.by_id(6882).is_none());
+        assert!(ITEM_CATALOG.by_id(0).is_none());
+
+        assert_eq!(ids(&ITEM_CATALOG.by_name(ItemSlot::Body, " octane ")), [23]);
+        assert!(ITEM_CATALOG.by_name(ItemSlot::Skin, "Octane").is_empty());
+
+        // the same name is used in different slots
+        let name = "007's Aston Martin DB5";
+        assert_eq!(ids(&ITEM_CATALOG.by_name(ItemSlot::Body, name)), [5547]);
+        assert_eq!(ids(&ITEM_CATALOG.by_name(ItemSlot::Wheels, name)), [5623]);
+        assert_eq!(ids(&ITEM_CATALOG.by_name(ItemSlot::PaintFinish, name)), [5657]);
+
+        assert!(ITEM_CATALOG.in_slot(ItemSlot::Hat).all(|item| item.slot == ItemSlot::Hat));
+        assert!(ITEM_CATALOG.in_slot(ItemSlot::Hat).any(|item| item.id == 224));
+    }
+
+    #[test]
+    fn search() {
+        let results = ITEM_CATALOG.search("Aston DB5", None);
+        assert!(results.len() > 4);
+        assert!(results[..4].iter().all(|item| item.name == "007's Aston Martin DB5"));
+        assert!(results.iter().any(|item| item.id == 6133 && item.slot == ItemSlot::Skin));
+
+        let results = ITEM_CATALOG.search("aston db5", Some(ItemSlot::Wheels));
+        assert_eq!(ids(&results), [5623]);
+
+        // the exact match comes before items that only contain the query
+        let results = ITEM_CATALOG.search("octane", Some(ItemSlot::Body));
+        assert_eq!(results[0].id, 23);
+
+        let results = ITEM_CATALOG.search("octane", Some(ItemSlot::Skin));
+        assert!(results.iter().any(|item| item.id == 302));
+        assert!(results.iter().all(|item| item.slot == ItemSlot::Skin && item.name.to_lowercase().contains("octane")));
+
+        assert!(ITEM_CATALOG.search("no item is called this", None).is_empty());
+    }
+
+    #[test]
+    fn validate_accepts_empty_ids() {
+        assert!(ITEM_CATALOG.validate(&looks(BotTeamLooksConfig::default(), BotTeamLooksConfig::default())).is_ok());
+
+        let team = BotTeamLooksConfig {
+            car_id: "23".to_owned(),
+            decal_id: "0".to_owned(),
+            wheels_id: " 5623 ".to_owned(),
+            antenna_id: "1".to_owned(),
+            hat_id: "".to_owned(),
+            paint_finish_id: "266".to_owned(),
+            custom_finish_id: "0".to_owned(),
+            engine_audio_id: "1786".to_owned(),
+            trails_id: "1898".to_owned(),
+            goal_explosion_id: "1903".to_owned(),
+            ..Default::default()
+        };
+        assert!(ITEM_CATALOG.validate(&looks(team.clone(), team)).is_ok());
+    }
+
+    #[test]
+    fn validate_unknown_id() {
+        let team = BotTeamLooksConfig {
+            boost_id: "999999".to_owned(),
+            ..Default::default()
+        };
+
+        let error = ITEM_CATALOG.validate(&looks(BotTeamLooksConfig::default(), team)).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                LoadoutError::UnknownItem {
+                    team: "Orange",
+                    slot: "boost",
+                    id: 999_999
+                }
+            ),
+            "{error:?}"
+        );
+
+        // an ID that exists, but not as a loadout item
+        let team = BotTeamLooksConfig {
+            hat_id: "6882".to_owned(),
+            ..Default::default()
+        };
+
+        let error = ITEM_CATALOG.validate(&looks(team, BotTeamLooksConfig::default())).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                LoadoutError::UnknownItem {
+                    team: "Blue",
+                    slot: "hat",
+                    id: 6882
+                }
+            ),
+            "{error:?}"
+        );
+
+        let team = BotTeamLooksConfig {
+            car_id: "octane".to_owned(),
+            ..Default::default()
+        };
+
+        let error = ITEM_CATALOG.validate(&looks(team, BotTeamLooksConfig::default())).unwrap_err();
+        assert!(
+            matches!(error, LoadoutError::NotANumber { team: "Blue", slot: "car", ref id } if id == "octane"),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn validate_wrong_slot() {
+        let team = BotTeamLooksConfig {
+            car_id: "1".to_owned(),
+            ..Default::default()
+        };
+
+        let error = ITEM_CATALOG.validate(&looks(team, BotTeamLooksConfig::default())).unwrap_err();
+        assert!(
+            matches!(error, LoadoutError::WrongSlot { team: "Blue", slot: "car", id: 1, kind: "antenna", ref name } if name == "8-Ball"),
+            "{error:?}"
+        );
+        assert_eq!(error.to_string(), "Blue car 1 is the antenna item \"8-Ball\", not a car item");
+
+        // paint finishes are checked in both finish slots
+        let team = BotTeamLooksConfig {
+            custom_finish_id: "23".to_owned(),
+            ..Default::default()
+        };
+
+        let error = ITEM_CATALOG.validate(&looks(BotTeamLooksConfig::default(), team)).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                LoadoutError::WrongSlot {
+                    team: "Orange",
+                    slot: "paint finish",
+                    id: 23,
+                    kind: "car",
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+    }
+}
@@ -1,10 +1,16 @@
 use crate::{
-    bot_management::cfg_helper::save_cfg,
+    bot_management::{
+        cfg_helper::{save_cfg, save_cfg_sync},
+        folder_watcher::watch_bot_folders,
+    },
     ccprintln,
     config_handles::{load_gui_config, load_gui_config_sync},
     custom_maps::convert_to_path,
     get_config_path,
-    rlbot::parsing::{bot_config_bundle::ScriptConfigBundle, match_settings_config_parser::*},
+    rlbot::parsing::{
+        bot_config_bundle::{BotConfigBundle, ScriptConfigBundle},
+        match_settings_config_parser::*,
+    },
 };
 use configparser::ini::Ini;
 use core::fmt;
@@ -12,7 +18,6 @@ use futures_util::future::join_all;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt::Debug, str::FromStr};
 use tauri::Window;
-use tokio::fs as async_fs;
 
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -57,9 +62,11 @@ impl BotFolders {
         conf.set("bot_folder_settings", "files", serde_json::to_string(&self.files).ok());
         conf.set("bot_folder_settings", "folders", serde_json::to_string(&self.folders).ok());
 
-        if let Err(e) = conf.write(get_config_path()) {
+        if let Err(e) = save_cfg_sync(&conf, get_config_path()) {
             ccprintln!(window, "Error writing config file: {e}");
         }
+
+        watch_bot_folders(window, self);
     }
 
     pub fn add_folder(&mut self, window: &Window, path: String) {
@@ -215,6 +222,25 @@ impl Default for MiniMatchConfig {
     }
 }
 
+impl From<&MatchConfig> for MiniMatchConfig {
+    fn from(config: &MatchConfig) -> Self {
+        Self {
+            map: config.map.clone(),
+            game_mode: config.game_mode,
+            match_behavior: config.match_behavior,
+            skip_replays: config.skip_replays,
+            instant_start: config.instant_start,
+            enable_lockstep: config.enable_lockstep,
+            randomize_map: config.randomize_map,
+            enable_rendering: config.enable_rendering,
+            enable_state_setting: config.enable_state_setting,
+            auto_save_replay: config.auto_save_replay,
+            scripts: config.scripts.iter().map(|script| MiniScriptBundle { path: script.path.clone() }).collect(),
+            mutators: config.mutators.clone(),
+        }
+    }
+}
+
 impl MiniMatchConfig {
     pub fn setup_for_start_match(&self, window: &Window, bf: &HashMap<String, BotFolder>) -> Result<Self, String> {
         let mut new = self.clone();
@@ -340,7 +366,7 @@ impl MatchConfig {
         let mut conf = load_gui_config(window).await;
         self.save_to_config(&mut conf);
 
-        if let Err(e) = async_fs::write(get_config_path(), conf.writes()).await {
+        if let Err(e) = save_cfg(&conf, get_config_path()).await {
             ccprintln!(window, "Error writing config file: {e}");
         }
     }
@@ -455,7 +481,7 @@ impl LauncherConfig {
         config.set("launcher_settings", "use_login_tricks", Some(self.use_login_tricks.to_string()));
         config.set("launcher_settings", "rocket_league_exe_path", self.rocket_league_exe_path);
 
-        if let Err(e) = async_fs::write(get_config_path(), config.writes()).await {
+        if let Err(e) = save_cfg(&config, get_config_path()).await {
             ccprintln!(window, "Error writing config file: {e}");
         }
     }
@@ -475,6 +501,26 @@ pub struct TeamBotBundle {
     pub skill: f32,
     pub runnable_type: String,
     pub path: Option<String>,
+    /// If the bot can be started before the match begins, from the bot's config file
+    #[serde(default)]
+    pub supports_early_start: bool,
+    /// The highest tick rate that the bot wants to receive packets at, from the bot's config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum_tick_rate_preference: Option<u32>,
+}
+
+impl TeamBotBundle {
+    pub fn from_bot_bundle(bundle: &BotConfigBundle, team: Team) -> Self {
+        Self {
+            name: bundle.name.clone(),
+            team,
+            skill: bundle.skill.unwrap_or(1.0),
+            runnable_type: bundle.runnable_type.clone(),
+            path: Some(bundle.path.clone()),
+            supports_early_start: bundle.supports_early_start,
+            maximum_tick_rate_preference: bundle.maximum_tick_rate_preference,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
@@ -606,7 +652,7 @@ impl StoryState {
         let mut conf = load_gui_config_sync(window);
         conf.set("story_mode", "save_state", serde_json::to_string(self).ok());
 
-        if let Err(e) = conf.write(get_config_path()) {
+        if let Err(e) = save_cfg_sync(&conf, get_config_path()) {
             ccprintln!(window, "Error writing config: {e}");
         }
     }
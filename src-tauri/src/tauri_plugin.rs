@@ -1,4 +1,4 @@
-use crate::{commands::shut_down_match_handler, InternalConsoleError, CONSOLE_TEXT_OUT_QUEUE};
+use crate::{commands::shut_down_match_handler, console::jobs, InternalConsoleError, CONSOLE_TEXT_OUT_QUEUE};
 use tauri::{
     async_runtime::block_on as tauri_block_on,
     plugin::{Builder as PluginBuilder, TauriPlugin},
@@ -7,6 +7,7 @@ use tauri::{
 
 pub fn close() -> Result<(), String> {
     tauri_block_on(async { shut_down_match_handler().await })?;
+    jobs::kill_all_jobs().map_err(|e| e.to_string())?;
     Ok(())
 }
 